@@ -0,0 +1,24 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::types::database::CanDatabase;
+
+/// Reports CAN ids defined by more than one of the given databases.
+///
+/// Each entry pairs the colliding id with the names of the owning databases, in the
+/// order they appear in `dbs`. Entries are sorted by id; ids repeated inside a single
+/// database are not reported here.
+pub fn id_collisions(dbs: &[&CanDatabase]) -> Vec<(u32, Vec<String>)> {
+    let mut owners: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+
+    for db in dbs {
+        let ids: BTreeSet<u32> = db.iter_messages().map(|m| m.id).collect();
+        for id in ids {
+            owners.entry(id).or_default().push(db.name.clone());
+        }
+    }
+
+    owners
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .collect()
+}
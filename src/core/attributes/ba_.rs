@@ -49,10 +49,7 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     }
 
     if attr_name == "BusType" {
-        db.bustype = match value {
-            "CAN FD" => BusType::CanFd,
-            _ => BusType::Can,
-        };
+        db.bustype = BusType::from_label(value);
     }
 
     if let Some(attr_spec) = db.attr_spec.get_mut(attr_name) {
@@ -1,6 +1,7 @@
 use crate::types::{
-    attributes::{AttrValueType, AttributeSpec, AttributeValue},
+    attributes::{AttrValueType, AttributeValue},
     database::CanDatabase,
+    message::{CanMessage, FrameFormat},
 };
 
 /// Decodes a message-level `BA_` assignment.
@@ -52,10 +53,20 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         rest
     };
 
-    // 7) immutable borrow to Attribute Specification
-    let attr_spec: &AttributeSpec = match db.attr_spec.get(attr_name) {
-        Some(spec) => spec,
-        None => return, // exit immediately
+    // 7) immutable borrow to Attribute Specification; without a definition only the
+    //    typed message fields are filled, from the value as written
+    let Some(attr_spec) = db.attr_spec.get(attr_name) else {
+        let undefined: AttributeValue = if rest.starts_with('"') {
+            AttributeValue::Str(value.to_string())
+        } else if let Ok(num) = value.parse::<i64>() {
+            AttributeValue::Int(num)
+        } else {
+            return;
+        };
+        if let Some(msg) = db.get_message_by_id_mut(msg_id) {
+            set_typed_field(msg, attr_name, &undefined, value);
+        }
+        return;
     };
 
     // 8) immutable borrow to Attribute Definition
@@ -93,9 +104,44 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     };
 
     // 9) assign the value (lookup by ID)
-    if let Some(msg) = db.get_message_by_id_mut(msg_id)
-        && let Some(slot) = msg.attributes.get_mut(attr_name)
-    {
+    let Some(msg) = db.get_message_by_id_mut(msg_id) else {
+        return;
+    };
+    set_typed_field(msg, attr_name, &attr_value, value);
+    if let Some(slot) = msg.attributes.get_mut(attr_name) {
         *slot = attr_value;
     }
 }
+
+/// Mirrors `VFrameFormat`, `GenMsgSendType` and the `GenMsg*` timings into the typed
+/// `CanMessage` fields. `raw` is the value as written: ENUM assignments are indexes, which
+/// for `VFrameFormat` are the frame format codes. The send type is stored by name.
+fn set_typed_field(msg: &mut CanMessage, attr_name: &str, value: &AttributeValue, raw: &str) {
+    let number: Option<i64> = match value {
+        AttributeValue::Int(num) => Some(*num),
+        AttributeValue::Hex(num) => i64::try_from(*num).ok(),
+        AttributeValue::Float(num) if num.fract() == 0.0 => Some(*num as i64),
+        AttributeValue::Float(_) => None,
+        AttributeValue::Enum(_) => raw.parse().ok(),
+        AttributeValue::Str(text) => text.parse().ok(),
+    };
+    let millis: Option<u32> = number.and_then(|num| u32::try_from(num).ok());
+
+    match attr_name {
+        "VFrameFormat" => {
+            if let Some(frame_format) = number.and_then(FrameFormat::from_code) {
+                msg.set_frame_format(frame_format);
+            }
+        }
+        "GenMsgSendType" => {
+            if let AttributeValue::Enum(send_type) | AttributeValue::Str(send_type) = value {
+                msg.send_type = Some(send_type.clone());
+            }
+        }
+        "GenMsgDelayTime" if millis.is_some() => msg.delay_time = millis,
+        "GenMsgCycleTime" if millis.is_some() => msg.cycle_time = millis,
+        "GenMsgStartDelayTime" if millis.is_some() => msg.start_delay_time = millis,
+        "GenMsgNrOfRepetition" if millis.is_some() => msg.nr_of_repetition = millis,
+        _ => {}
+    }
+}
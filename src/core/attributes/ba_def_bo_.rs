@@ -93,6 +93,11 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         _ => {}
     }
 
+    if attr_spec.normalize_bounds() {
+        db.parse_warnings
+            .push(format!("BA_DEF_ BO_ \"{name}\": min > max, bounds swapped"));
+    }
+
     attr_spec.name = name.to_string();
     attr_spec.type_of_object = AttrObject::Message;
     db.attr_spec.insert(name.to_string(), attr_spec);
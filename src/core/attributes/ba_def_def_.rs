@@ -49,11 +49,15 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
             Err(_) => None,
         },
         AttrValueType::Enum => {
-            // Only accept if it's one of the enum variants
+            // Only accept one of the enum variants, by label or by index (as written on save)
             if spec.enum_values.iter().any(|s| s == value_raw) {
                 Some(AttributeValue::Enum(value_raw.to_string()))
             } else {
-                None
+                value_raw
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|idx| spec.enum_values.get(idx))
+                    .map(|label| AttributeValue::Enum(label.clone()))
             }
         }
     };
@@ -89,6 +89,11 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         _ => {}
     }
 
+    if attr_spec.normalize_bounds() {
+        db.parse_warnings
+            .push(format!("BA_DEF_REL_ \"{name}\": min > max, bounds swapped"));
+    }
+
     match relation {
         "BU_SG_REL_" => {
             attr_spec.name = name.to_string();
@@ -7,7 +7,9 @@ use crate::types::{
 /// Decodes a `SIG_VALTYPE_` line assigning floating-point encodings to a signal.
 ///
 /// Shape: `SIG_VALTYPE_ <MsgID> <SignalName> : <Value>;`
-/// where `<Value>` is `1` (IEEE float, 32-bit) or `2` (IEEE double, 64-bit).
+/// where `<Value>` is `0` (integer), `1` (IEEE float, 32-bit) or `2` (IEEE double, 64-bit);
+/// some tools write `3` for double as well. The colon may be attached to either neighbour.
+/// Code `0` leaves the signal as its `SG_` line declared it.
 pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     let mut parts = line.trim_end_matches(';').split_ascii_whitespace();
 
@@ -25,24 +27,17 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         return;
     };
 
-    // 3) <SignalName>
-    let signal_name: &str = match parts.next() {
-        Some(name) => name,
-        None => return,
+    // 3) <SignalName> [:] <Value>, with the colon possibly attached ("Sig:", ":1")
+    let rest: String = parts.collect::<Vec<_>>().join(" ");
+    let Some((name_part, value_part)) = rest.split_once(':') else {
+        return;
     };
-
-    // 4) skip ':'
-    match parts.next() {
-        Some(":") => {}
-        _ => return,
+    let signal_name: &str = name_part.trim();
+    let value: &str = value_part.trim();
+    if signal_name.is_empty() {
+        return;
     }
 
-    // 5) <Value>
-    let value: &str = match parts.next() {
-        Some(val) => val,
-        None => return,
-    };
-
     // 6) assign the Sign property to specific sisignal
     let sig_key_opt: Option<CanSignalKey> = {
         let msg: &CanMessage = match db.get_message_by_id(msg_id) {
@@ -59,7 +54,7 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         && let Some(sig) = db.get_sig_by_key_mut(sk)
     {
         match value {
-            "2" => {
+            "2" | "3" => {
                 sig.sign = Signess::IeeeDouble;
                 sig.bit_length = 64;
             }
@@ -67,7 +62,10 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
                 sig.sign = Signess::IeeeFloat;
                 sig.bit_length = 32;
             }
-            _ => {}
+            // integer (and unknown codes): the SG_ line already set sign and length
+            _ => return,
         }
+        sig.steps.clear();
+        sig.compile_inline();
     }
 }
@@ -1,7 +1,12 @@
-use crate::types::database::CanDatabase;
+use crate::types::{
+    database::{CanDatabase, CanNodeKey},
+    node::PLACEHOLDER_NODE,
+};
 
 /// Decode a `BO_` line robustly using `:` as separator between name and length.
 /// Accepts both: `BO_ 123 NAME: 8 Node` and `BO_ 123 NAME : 8 Node`.
+///
+/// A transmitter missing from `BU_` is created as a node and reported in `parse_warnings`.
 pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     let line: &str = line.trim();
     if !line.starts_with("BO_") {
@@ -15,24 +20,54 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     let mut split_once = after.splitn(2, char::is_whitespace);
     let id_str: &str = split_once.next().unwrap_or("0");
     let rest: &str = split_once.next().unwrap_or("").trim();
-    let id: u32 = id_str.parse::<u32>().unwrap_or(0);
+    let id: u32 = match id_str.parse::<u32>() {
+        Ok(id) => id,
+        Err(_) => {
+            db.line_malformed = true;
+            db.current_msg = None; // keep its SG_ rows off the previous message
+            return;
+        }
+    };
 
     // 2) NAME (everything up to the first ':')
     let colon_pos: usize = match rest.find(':') {
         Some(p) => p,
-        None => return,
+        None => {
+            db.line_malformed = true;
+            db.current_msg = None;
+            return;
+        }
     };
     let name: String = rest[..colon_pos].trim().trim_end_matches(':').to_string();
 
     // 3) After ':' → <len> <sender?>
     let mut it = rest[colon_pos + 1..].trim().split_ascii_whitespace();
-    let byte_length: u16 = it.next().and_then(|t| t.parse::<u16>().ok()).unwrap_or(0);
+    let byte_length: u16 = match it.next().and_then(|t| t.parse::<u16>().ok()) {
+        Some(len) => len,
+        None => {
+            db.line_malformed = true;
+            0
+        }
+    };
     let sender_name: &str = it.next().unwrap_or("").trim_end_matches(';');
 
     // create the message
-    if let Ok(msg_key) = db.add_message(&name, id, byte_length) {
-        // if Result Ok, add sender_node
-        if let Some(node_key) = db.get_node_key_by_name(sender_name) {
+    if let Ok(msg_key) = db.add_message_unchecked(&name, id, byte_length) {
+        // if Result Ok, add sender_node (declaring it when BU_ did not)
+        let node_key: Option<CanNodeKey> = match db.get_node_key_by_name(sender_name) {
+            Some(node_key) => Some(node_key),
+            None if !sender_name.is_empty() && sender_name != PLACEHOLDER_NODE => {
+                let created: Option<CanNodeKey> = db.add_node(sender_name).ok();
+                if created.is_some() {
+                    db.parse_warnings.push(format!(
+                        "BO_ {id} \"{name}\": transmitter \"{sender_name}\" is not declared in BU_, node created"
+                    ));
+                }
+                created
+            }
+            None => None,
+        };
+        if let Some(node_key) = node_key {
             let _ = db.add_sender_relation(msg_key, node_key);
         }
     }
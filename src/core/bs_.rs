@@ -0,0 +1,34 @@
+use crate::types::database::{BitTiming, CanDatabase};
+
+/// Decode the `BS_` bit timing line. Every part is optional; an empty section leaves
+/// `bit_timing` unset.
+/// Example: `BS_: 500000:1,2`
+pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
+    let Some(rest) = line.trim().strip_prefix("BS_") else {
+        return;
+    };
+    let rest: &str = rest.trim_start().trim_start_matches(':');
+    let rest: String = rest
+        .trim_end_matches(';')
+        .split_ascii_whitespace()
+        .collect();
+    if rest.is_empty() {
+        return;
+    }
+
+    // "<baudrate>:<btr1>,<btr2>"
+    let mut baud_and_btr = rest.splitn(2, ':');
+    let baudrate: u32 = baud_and_btr
+        .next()
+        .and_then(|b| b.parse().ok())
+        .unwrap_or(0);
+    let mut btr = baud_and_btr.next().unwrap_or("").split(',');
+    let btr1: u8 = btr.next().and_then(|b| b.parse().ok()).unwrap_or(0);
+    let btr2: u8 = btr.next().and_then(|b| b.parse().ok()).unwrap_or(0);
+
+    db.bit_timing = Some(BitTiming {
+        baudrate,
+        btr1,
+        btr2,
+    });
+}
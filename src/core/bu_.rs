@@ -1,4 +1,4 @@
-use crate::types::database::CanDatabase;
+use crate::types::{database::CanDatabase, node::PLACEHOLDER_NODE};
 
 /// Decode the BU_ line listing node names and register them in the database.
 /// Example: `BU_: ECU1 ECU2 ECU3`
@@ -12,7 +12,8 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
 
     for name in parts {
         let name = name.trim();
-        if !name.is_empty() {
+        // the placeholder is not a real node
+        if !name.is_empty() && name != PLACEHOLDER_NODE {
             // creates the node and ignore the NodeKey returned
             let _ = db.add_node(name);
         }
@@ -1,4 +1,4 @@
-use crate::types::database::CanDatabase;
+use crate::{core::strings::extract_quoted_unescaped, types::database::CanDatabase};
 
 /// `CM_ BO_ <ID> "Comment...";`
 pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
@@ -15,18 +15,11 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         return;
     }
 
-    let line: &str = line.trim_end_matches(';').trim();
-    let first: usize = match line.find('\"') {
-        Some(p) => p,
-        None => return,
-    };
-    let last: usize = match line.rfind('\"') {
-        Some(p) if p > first => p,
-        _ => return,
+    let Some(comment) = extract_quoted_unescaped(line) else {
+        return;
     };
-    let comment: &str = &line[first + 1..last];
 
     if let Some(msg) = db.get_message_by_id_mut(id) {
-        msg.comment = comment.to_string();
+        msg.comment = comment;
     }
 }
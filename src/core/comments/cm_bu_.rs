@@ -1,4 +1,4 @@
-use crate::types::database::CanDatabase;
+use crate::{core::strings::extract_quoted_unescaped, types::database::CanDatabase};
 
 /// Parse a node-level comment:
 /// `CM_ BU_ NodeName "Comment..."`
@@ -15,16 +15,10 @@ pub(crate) fn decode(db: &mut CanDatabase, text: &str) {
         None => return,
     };
 
-    // Extract the quoted comment as-is (preserving inner spaces/newlines)
-    let first_quote = match text.find('\"') {
-        Some(p) => p,
-        None => return,
-    };
-    let last_quote = match text.rfind('\"') {
-        Some(p) if p > first_quote => p,
-        _ => return,
+    // Extract the quoted comment (preserving inner spaces/newlines, resolving escapes)
+    let Some(comment) = extract_quoted_unescaped(text) else {
+        return;
     };
-    let comment = text[first_quote + 1..last_quote].to_string();
 
     // Update single source of truth
     if let Some(node) = db.get_node_by_name_mut(node_name) {
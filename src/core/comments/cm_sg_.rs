@@ -1,6 +1,9 @@
-use crate::types::{
-    database::{CanDatabase, CanSignalKey},
-    message::CanMessage,
+use crate::{
+    core::strings::extract_quoted_unescaped,
+    types::{
+        database::{CanDatabase, CanSignalKey},
+        message::CanMessage,
+    },
 };
 
 /// Parse a signal-level comment:
@@ -34,9 +37,8 @@ pub(crate) fn decode(db: &mut CanDatabase, text: &str) {
     // Now take a mutable borrow of `db` to update the comment.
     if let Some(sig_key) = sig_key_opt
         && let Some(s) = db.get_sig_by_key_mut(sig_key)
-        && let (Some(first), Some(last)) = (text.find('"'), text.rfind('"'))
-        && last > first
+        && let Some(comment) = extract_quoted_unescaped(text)
     {
-        s.comment = text[first + 1..last].to_string();
+        s.comment = comment;
     }
 }
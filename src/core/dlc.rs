@@ -0,0 +1,74 @@
+//! CAN / CAN FD DLC (Data Length Code) helpers.
+//!
+//! The DLC field is 4 bits wide. Codes `0..=8` map 1:1 to the payload length,
+//! while codes `9..=15` are only meaningful on CAN FD and map to the larger
+//! payload sizes `12, 16, 20, 24, 32, 48, 64`.
+
+/// Payload length in bytes indexed by DLC code (`0..=15`).
+const DLC_TO_LENGTH: [u16; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Returns the payload length (bytes) encoded by a CAN FD DLC `code`.
+///
+/// Codes above 15 cannot be represented in the 4-bit DLC field and saturate to 64.
+pub fn code_to_length(code: u8) -> u16 {
+    DLC_TO_LENGTH[code.min(15) as usize]
+}
+
+/// Returns the DLC code for an exact payload length, or `None` if `len` is not a
+/// legal CAN/CAN FD payload size (e.g. `9` or `13`).
+pub fn length_to_code(len: u16) -> Option<u8> {
+    DLC_TO_LENGTH
+        .iter()
+        .position(|&l| l == len)
+        .map(|code| code as u8)
+}
+
+/// Rounds `len` up to the next legal CAN/CAN FD payload length (e.g. `9` → `12`).
+///
+/// Lengths above 64 saturate to 64.
+pub fn can_fd_round_up(len: u16) -> u16 {
+    DLC_TO_LENGTH
+        .iter()
+        .copied()
+        .find(|&l| l >= len)
+        .unwrap_or(64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_to_length_covers_every_code() {
+        let expected: [u16; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+        for (code, &len) in expected.iter().enumerate() {
+            assert_eq!(code_to_length(code as u8), len, "code {code}");
+        }
+        assert_eq!(code_to_length(16), 64);
+        assert_eq!(code_to_length(u8::MAX), 64);
+    }
+
+    #[test]
+    fn length_to_code_round_trips_every_code() {
+        for code in 0u8..=15 {
+            assert_eq!(length_to_code(code_to_length(code)), Some(code));
+        }
+    }
+
+    #[test]
+    fn length_to_code_rejects_illegal_lengths() {
+        for len in [9, 10, 11, 13, 17, 33, 63, 65, 100] {
+            assert_eq!(length_to_code(len), None, "length {len}");
+        }
+    }
+
+    #[test]
+    fn can_fd_round_up_picks_the_next_legal_length() {
+        assert_eq!(can_fd_round_up(9), 12);
+        assert_eq!(can_fd_round_up(12), 12);
+        assert_eq!(can_fd_round_up(13), 16);
+        assert_eq!(can_fd_round_up(33), 48);
+        assert_eq!(can_fd_round_up(8), 8);
+        assert_eq!(can_fd_round_up(65), 64);
+    }
+}
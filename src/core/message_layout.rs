@@ -4,10 +4,11 @@ use crate::types::signal::Endianness;
 /// Verify that (bit_start, bit_length) fits within the frame defined by DLC.
 /// Returns Ok(()) if the signal fits; Err(...) with the reason otherwise.
 ///
-/// DBC assumptions:
-/// - Intel: the field occupies bits [start, start + len - 1] on a linear 0..(8*bytes-1) plane.
-/// - Motorola: map DBC bit_start to linear index `lin = (start & !7) + (7 - (start & 7))`,
-///   then the field advances backwards: [lin - (len-1) .. lin].
+/// DBC assumptions (bit `n` is bit `n % 8` of byte `n / 8`, bit 0 being the byte LSB):
+/// - Intel: `bit_start` is the LSB; the field occupies bits [start, start + len - 1].
+/// - Motorola: `bit_start` is the MSB; the field walks down to bit 0 of its byte, then
+///   continues from bit 7 of the next byte. On the MSB-first plane
+///   `lin = (start & !7) + (7 - (start & 7))` it occupies [lin, lin + len - 1].
 pub fn check_signal_fits(
     dlc: u16,
     bit_start: u16,
@@ -34,10 +35,10 @@ pub fn check_signal_fits(
             }
         }
         Endianness::Motorola => {
-            // Map DBC start (MSB-first within a byte) to linear LSB-first index
+            // Map the DBC start (MSB) to the MSB-first plane, where the field grows forward
             let s: usize = bit_start as usize;
-            let linearized_start: usize = (s & !7) + (7 - (s & 7)); // e.g., start=0 -> 7, start=7 -> 0, start=8 -> 15, etc.
-            let linearized_end: isize = linearized_start as isize - (bit_length as isize - 1);
+            let linearized_start: usize = (s & !7) + (7 - (s & 7)); // e.g., start=7 -> 0, start=0 -> 7, start=15 -> 8, etc.
+            let linearized_end: usize = linearized_start + (bit_length as usize) - 1;
 
             if linearized_start >= total_bits {
                 return Err(MessageLayoutError::MotorolaStartOutOfBounds {
@@ -46,14 +47,68 @@ pub fn check_signal_fits(
                     dlc,
                 });
             }
-            if linearized_end < 0 {
+            if linearized_end >= total_bits {
                 return Err(MessageLayoutError::MotorolaEndOutOfBounds {
                     end: linearized_end,
+                    total_bits,
                     dlc,
                 });
             }
-            // Safe: (linearized_end as usize) < total_bits because linearized_end >= 0 and linearized_end <= linearized_start < total_bits
             Ok(())
         }
     }
 }
+
+/// Returns the absolute frame bit indices covered by a signal, LSB of the value first.
+///
+/// Bit indices follow the same numbering as [`check_signal_fits`] (bit `n` is bit `n % 8`
+/// of byte `n / 8`). Intel fields are contiguous from `bit_start`; Motorola fields start
+/// at the MSB `bit_start` and are walked back, so the returned list ends with it.
+pub fn signal_bit_positions(bit_start: u16, bit_length: u16, endian: Endianness) -> Vec<u16> {
+    match endian {
+        Endianness::Intel => (0..bit_length).map(|i| bit_start + i).collect(),
+        Endianness::Motorola => {
+            // Walk MSB → LSB, jumping to bit 7 of the next byte after bit 0
+            let mut positions: Vec<u16> = Vec::with_capacity(bit_length as usize);
+            let mut pos: u16 = bit_start;
+            for _ in 0..bit_length {
+                positions.push(pos);
+                pos = if pos.is_multiple_of(8) {
+                    pos + 15
+                } else {
+                    pos - 1
+                };
+            }
+            positions.reverse();
+            positions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intel_positions_run_up_from_the_start_bit() {
+        assert_eq!(
+            signal_bit_positions(3, 10, Endianness::Intel),
+            [3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+        );
+        assert!(signal_bit_positions(0, 0, Endianness::Intel).is_empty());
+    }
+
+    #[test]
+    fn motorola_positions_end_at_the_msb() {
+        // MSB at bit 3: byte0[3..0] ++ byte1[7..0], listed LSB first
+        assert_eq!(
+            signal_bit_positions(3, 12, Endianness::Motorola),
+            [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3]
+        );
+        // within one byte
+        assert_eq!(
+            signal_bit_positions(13, 4, Endianness::Motorola),
+            [10, 11, 12, 13]
+        );
+    }
+}
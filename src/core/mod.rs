@@ -1,10 +1,15 @@
 pub(crate) mod attributes;
 pub(crate) mod bo_;
 pub(crate) mod bo_tx_bu_;
+pub(crate) mod bs_;
 pub(crate) mod bu_;
 pub(crate) mod comments;
+pub mod dlc;
 pub mod message_layout;
 pub(crate) mod sg_;
+pub(crate) mod sgtype_;
+pub(crate) mod sig_type_ref_;
 pub(crate) mod strings;
 pub(crate) mod val_;
+pub(crate) mod val_table_;
 pub(crate) mod version;
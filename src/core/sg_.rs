@@ -1,6 +1,7 @@
 use crate::types::{
     database::{CanDatabase, CanMessageKey, CanNodeKey, CanSignalKey},
     message::{MuxRole, MuxSelector},
+    node::PLACEHOLDER_NODE,
     signal::{Endianness, Signess},
 };
 
@@ -9,6 +10,7 @@ use crate::types::{
 /// SG_ <name> [M|mX]: <bit_start>|<bit_length>@<endian><sign> (<factor>,<offset>) [<min>|<max>] "<unit>" <receivers...>
 pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     if db.messages.is_empty() {
+        db.line_malformed = true;
         return;
     }
 
@@ -25,6 +27,7 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
 
     let name: String = name_token.to_string();
     if name.is_empty() {
+        db.line_malformed = true;
         return;
     }
 
@@ -44,7 +47,7 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     }
 
     // right part analysis <bit_start>|<bit_length>@<endian><sign> (<factor>,<offset>) [<min>|<max>] "<unit>" <receivers...>
-    let mut it = right.split_ascii_whitespace();
+    let mut it = right.split_ascii_whitespace().peekable();
 
     // 1) bit info: "63|1@1+"
     let bit_info: &str = it.next().unwrap_or("");
@@ -52,13 +55,30 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     let bit_pos_len: &str = bit_and_rest.next().unwrap_or(""); // "63|1"
     let es: &str = bit_and_rest.next().unwrap_or(""); // "1+"
     let mut pos_len = bit_pos_len.split('|');
-    let bit_start: u16 = pos_len.next().unwrap_or("0").parse().unwrap_or(0);
-    let bit_length: u16 = pos_len.next().unwrap_or("0").parse().unwrap_or(0);
+    let bit_start: Option<u16> = pos_len.next().and_then(|t| t.parse().ok());
+    let bit_length: Option<u16> = pos_len.next().and_then(|t| t.parse().ok());
+    if bit_start.is_none() || bit_length.is_none() || !es.starts_with(['0', '1']) {
+        db.line_malformed = true;
+    }
+    let bit_start: u16 = bit_start.unwrap_or(0);
+    let bit_length: u16 = bit_length.unwrap_or(0);
     let endian_value: u8 = es.chars().next().unwrap_or('1').to_digit(10).unwrap_or(1) as u8;
-    let sign: Signess = if es.chars().nth(1).unwrap_or('+') == '-' {
-        Signess::Signed
-    } else {
-        Signess::Unsigned
+    // the sign may be detached by stray whitespace ("@1 +") or missing altogether ("@1")
+    let sign_char: Option<char> = match es.chars().nth(1) {
+        Some(c) => Some(c),
+        None => it
+            .next_if(|&tok| tok == "+" || tok == "-")
+            .and_then(|tok| tok.chars().next()),
+    };
+    let sign: Signess = match sign_char {
+        Some('-') => Signess::Signed,
+        Some(_) => Signess::Unsigned,
+        None => {
+            db.parse_warnings.push(format!(
+                "SG_ \"{name}\": missing sign after '@{endian_value}', assuming unsigned"
+            ));
+            Signess::Unsigned
+        }
     };
 
     let endian: Endianness = if endian_value == 1 {
@@ -142,7 +162,7 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         .unwrap_or(&unit_of_measurement)
         .to_string();
 
-    // 5) receivers (space-separated)
+    // 5) receivers (comma-separated), `Vector__XXX` meaning "no receiver"
     let recv_opt: Option<&str> = it.next();
     let mut receiver_nodes: Vec<CanNodeKey> = Vec::with_capacity(
         recv_opt
@@ -151,6 +171,9 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     );
     if let Some(recv) = recv_opt {
         for node_name in recv.split(",") {
+            if node_name == PLACEHOLDER_NODE {
+                continue;
+            }
             if let Some(key) = db.get_node_key_by_name(node_name) {
                 receiver_nodes.push(key);
             }
@@ -158,7 +181,8 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     }
 
     // create the signal
-    let sig_key: CanSignalKey = db.add_signal(&name, endian, sign, factor, offset, min, max, &unit);
+    let sig_key: CanSignalKey =
+        db.add_signal_unchecked(&name, endian, sign, factor, offset, min, max, &unit);
 
     // map bit_start and bit_length info
     let Some(signal) = db.get_sig_by_key_mut(sig_key) else {
@@ -187,5 +211,5 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
     };
     db.current_msg = Some(msg_key);
 
-    let _ = db.add_msg_sig_relation(sig_key, msg_key, mux_role, mux_selector);
+    let _ = db.add_msg_sig_relation_unchecked(sig_key, msg_key, mux_role, mux_selector);
 }
@@ -0,0 +1,50 @@
+use crate::{
+    core::{strings::statement_body, val_::parse_value_pairs},
+    types::database::CanDatabase,
+};
+
+/// Parse a SGTYPE_ line declaring a signal type, keeping only its value table reference:
+/// `SGTYPE_ <TypeName> : <size>@<order><sign> (<factor>,<offset>) [<min>|<max>] "<unit>" <default> , <ValueTable> ;`
+pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
+    let body: &str = statement_body(line);
+    let mut tokens = body.split_ascii_whitespace();
+    if tokens.next() != Some("SGTYPE_") {
+        return;
+    }
+    let Some(type_name) = tokens.next().map(|n| n.trim_end_matches(':')) else {
+        return;
+    };
+    if type_name.is_empty() {
+        return;
+    }
+
+    // the value table name follows the last comma and is a plain identifier
+    let value_table_name: Option<String> = body
+        .rsplit_once(',')
+        .map(|(_, name)| name.trim())
+        .filter(|name| !name.is_empty() && !name.contains(|c: char| c == '"' || c.is_whitespace()))
+        .map(str::to_string);
+
+    db.signal_types
+        .entry(type_name.to_string())
+        .or_default()
+        .value_table_name = value_table_name;
+}
+
+/// Parse a SGTYPE_VAL_ line describing the values of a signal type:
+/// `SGTYPE_VAL_ <TypeName> <value> "<desc>" ... ;`
+pub(crate) fn decode_val(db: &mut CanDatabase, line: &str) {
+    let mut tokens = statement_body(line).split_ascii_whitespace();
+    if tokens.next() != Some("SGTYPE_VAL_") {
+        return;
+    }
+    let Some(type_name) = tokens.next() else {
+        return;
+    };
+
+    let table = parse_value_pairs(tokens);
+    db.signal_types
+        .entry(type_name.to_string())
+        .or_default()
+        .value_table = table;
+}
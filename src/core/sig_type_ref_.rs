@@ -0,0 +1,34 @@
+use crate::{
+    core::strings::statement_body,
+    types::database::{CanDatabase, CanSignalKey},
+};
+
+/// Parse a SIG_TYPE_REF_ line linking a signal to its signal type:
+/// `SIG_TYPE_REF_ <MessageID> <SignalName> : <TypeName> ;`
+/// The colon may be attached to either neighbour.
+pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
+    let mut tokens = statement_body(line)
+        .split(|c: char| c.is_ascii_whitespace() || c == ':')
+        .filter(|t| !t.is_empty());
+    if tokens.next() != Some("SIG_TYPE_REF_") {
+        return;
+    }
+    let Some(Ok(message_id)) = tokens.next().map(str::parse::<u32>) else {
+        return;
+    };
+    let (Some(signal_name), Some(type_name)) = (tokens.next(), tokens.next()) else {
+        return;
+    };
+
+    let Some(sig_key) = db.get_message_by_id(message_id).and_then(|msg| {
+        msg.signals.iter().copied().find(|&sig_key: &CanSignalKey| {
+            db.get_sig_by_key(sig_key)
+                .is_some_and(|s| s.name == signal_name)
+        })
+    }) else {
+        return;
+    };
+    if let Some(signal) = db.get_sig_by_key_mut(sig_key) {
+        signal.signal_type = Some(type_name.to_string());
+    }
+}
@@ -1,4 +1,5 @@
-//! Utilities for parsing quoted strings in DBC files.
+//! Utilities for parsing quoted strings in DBC files, and for formatting numbers
+//! the way they are written back.
 //!
 //! These helpers support escaped quotes (`\"`) and multi-line quoted strings,
 //! which are common in `CM_` comments or attribute values.
@@ -57,3 +58,106 @@ pub(crate) fn collect_all_quoted(s: &str) -> Vec<String> {
 
     out
 }
+
+/// Extracts the first quoted segment (`"..."`) and resolves DBC escapes.
+///
+/// `\\`, `\"`, `\n`, `\r` and `\t` are unescaped (the inverse of the save-side
+/// escaping); any other backslash sequence is kept verbatim. Returns `None` if
+/// no complete quoted segment is present.
+pub(crate) fn extract_quoted_unescaped(s: &str) -> Option<String> {
+    let start: usize = s.find('"')?;
+    let mut out: String = String::new();
+    let mut chars = s[start + 1..].chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => return Some(out),
+            '\\' => match chars.next() {
+                Some('\\') => out.push('\\'),
+                Some('"') => out.push('"'),
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some(other) => {
+                    out.push('\\');
+                    out.push(other);
+                }
+                None => return None,
+            },
+            _ => out.push(ch),
+        }
+    }
+    None
+}
+
+/// Returns the statement up to (excluding) its first `;` outside quotes.
+///
+/// Anything after the terminator, such as `// exported by Tool`, is dropped. Lines without
+/// a terminator are returned unchanged.
+pub(crate) fn statement_body(s: &str) -> &str {
+    let mut in_quotes: bool = false;
+    let mut backslashes: usize = 0;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '\\' => {
+                backslashes += 1;
+                continue;
+            }
+            '"' if backslashes.is_multiple_of(2) => in_quotes = !in_quotes,
+            ';' if !in_quotes => return &s[..i],
+            _ => {}
+        }
+        backslashes = 0;
+    }
+    s
+}
+
+/// Formats floating-point values while stripping redundant trailing zeros.
+pub(crate) fn format_f64(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        let mut s = format!("{:.12}", value);
+        while s.contains('.') && s.ends_with('0') {
+            s.pop();
+        }
+        if s.ends_with('.') {
+            s.push('0');
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_quoted_unescaped_resolves_escapes() {
+        assert_eq!(
+            extract_quoted_unescaped(r#"CM_ BO_ 1 "He said \"hi\"; bye \\ end";"#).as_deref(),
+            Some(r#"He said "hi"; bye \ end"#)
+        );
+        assert_eq!(
+            extract_quoted_unescaped(r#""a\nb\tc\x""#).as_deref(),
+            Some("a\nb\tc\\x")
+        );
+        assert_eq!(extract_quoted_unescaped(r#"CM_ "unterminated"#), None);
+        assert_eq!(extract_quoted_unescaped("CM_ BO_ 1;"), None);
+    }
+
+    #[test]
+    fn statement_body_stops_at_the_first_unquoted_semicolon() {
+        assert_eq!(
+            statement_body(r#"VAL_ 960 Sig 0 "Off" 1 "On" ; // exported by Tool"#),
+            r#"VAL_ 960 Sig 0 "Off" 1 "On" "#
+        );
+        assert_eq!(
+            statement_body(r#"VAL_ 1 S 0 "a;b" 1 "c\";d";x"#),
+            r#"VAL_ 1 S 0 "a;b" 1 "c\";d""#
+        );
+        assert_eq!(
+            statement_body("VAL_TABLE_ T 0 \"x\""),
+            "VAL_TABLE_ T 0 \"x\""
+        );
+    }
+}
@@ -1,23 +1,47 @@
-use crate::types::database::CanDatabase;
+use crate::{core::strings::statement_body, types::database::CanDatabase};
 use std::collections::BTreeMap;
 
 /// Parse a VAL_ line that defines a value table for a specific signal:
 /// `VAL_ <MessageID> <SignalName> <value> "<desc>" ... ;`
+/// Anything after the terminating `;` is ignored.
+///
+/// The environment variable form `VAL_ <EnvVarName> <value> "<desc>" ... ;` (non-numeric
+/// second token) is kept verbatim in `unparsed_lines`, next to the `EV_` it describes.
 pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
-    let mut tokens = line.split_ascii_whitespace();
+    let mut tokens = statement_body(line).split_ascii_whitespace();
     if tokens.next().map(|s| s.to_ascii_lowercase()) != Some("val_".into()) {
         return;
     }
-    let message_id: u32 = tokens
-        .next()
-        .and_then(|t| t.parse::<u32>().ok())
-        .unwrap_or(0);
+    let Some(message_id) = tokens.next() else {
+        return;
+    };
+    let Ok(message_id) = message_id.parse::<u32>() else {
+        db.unparsed_lines.push(line.trim_end().to_string());
+        return;
+    };
     let signal_name = match tokens.next() {
         Some(n) => n,
         None => return,
     };
 
-    // Collect pairs: numeric value followed by quoted description
+    let table: BTreeMap<i32, String> = parse_value_pairs(tokens);
+
+    if let Some(msg) = db.get_message_by_id(message_id)
+        && let Some(&sig_key) = msg.signals.iter().find(|&&sig_key| {
+            db.get_sig_by_key(sig_key)
+                .is_some_and(|s| s.name == signal_name)
+        })
+        && let Some(s) = db.get_sig_by_key_mut(sig_key)
+    {
+        s.value_table = table;
+    }
+}
+
+/// Collects `<value> "<desc>"` pairs until `;` or the first malformed token.
+/// Shared by `VAL_` and `VAL_TABLE_`.
+pub(crate) fn parse_value_pairs<'a>(
+    tokens: impl Iterator<Item = &'a str>,
+) -> BTreeMap<i32, String> {
     let mut table: BTreeMap<i32, String> = BTreeMap::new();
     let mut t = tokens.peekable();
     while let Some(val_tok) = t.next() {
@@ -50,13 +74,5 @@ pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
         table.insert(val, desc);
     }
 
-    if let Some(msg) = db.get_message_by_id(message_id)
-        && let Some(&sig_key) = msg.signals.iter().find(|&&sig_key| {
-            db.get_sig_by_key(sig_key)
-                .is_some_and(|s| s.name == signal_name)
-        })
-        && let Some(s) = db.get_sig_by_key_mut(sig_key)
-    {
-        s.value_table = table;
-    }
+    table
 }
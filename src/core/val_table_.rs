@@ -0,0 +1,22 @@
+use crate::{
+    core::{strings::statement_body, val_::parse_value_pairs},
+    types::database::CanDatabase,
+};
+
+/// Parse a VAL_TABLE_ line that defines a reusable, named value table:
+/// `VAL_TABLE_ <TableName> <value> "<desc>" ... ;`
+pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
+    let mut tokens = statement_body(line).split_ascii_whitespace();
+    if tokens.next() != Some("VAL_TABLE_") {
+        return;
+    }
+    let Some(table_name) = tokens.next().map(|n| n.trim_end_matches(';')) else {
+        return;
+    };
+    if table_name.is_empty() {
+        return;
+    }
+
+    let table = parse_value_pairs(tokens);
+    db.value_tables.insert(table_name.to_string(), table);
+}
@@ -1,11 +1,45 @@
 use crate::types::database::CanDatabase;
 
 /// Parses the `VERSION` line and stores the version string on the database.
+///
+/// The text between the first and the last `"` is kept verbatim (case and spaces included).
 pub(crate) fn decode(db: &mut CanDatabase, line: &str) {
-    db.version = line
-        .to_lowercase()
-        .replace("version", "") // delete version text
-        .trim() // delete whitespaces
-        .trim_matches('"') // delete "
-        .to_string() // convert in string
+    let body: &str = line.trim_start();
+    let body: &str = body.get(..7).map_or(body, |keyword| {
+        if keyword.eq_ignore_ascii_case("VERSION") {
+            &body[7..]
+        } else {
+            body
+        }
+    });
+
+    db.version = match (body.find('"'), body.rfind('"')) {
+        (Some(first), Some(last)) if first < last => body[first + 1..last].to_string(),
+        _ => body.trim().trim_matches('"').to_string(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version_of(line: &str) -> String {
+        let mut db: CanDatabase = CanDatabase::default();
+        decode(&mut db, line);
+        db.version
+    }
+
+    #[test]
+    fn quoted_version_is_kept_verbatim() {
+        assert_eq!(
+            version_of(r#"VERSION "Rev 2.0 VERSION-B""#),
+            "Rev 2.0 VERSION-B"
+        );
+        assert_eq!(version_of(r#"VERSION """#), "");
+        assert_eq!(
+            version_of(r#"  VERSION "a "quoted" one"  "#),
+            r#"a "quoted" one"#
+        );
+        assert_eq!(version_of("VERSION 1.0"), "1.0");
+    }
 }
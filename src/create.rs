@@ -45,50 +45,14 @@ pub fn new_database(
         .insert("DBName".to_string(), AttributeValue::Str(db.name.clone()));
 
     // Fill in default BusType
-    let bustype_label: String = bustype.to_str();
-    let bustype_spec: AttributeSpec = AttributeSpec {
-        type_of_object: AttrObject::Database,
-        default: AttributeValue::Str("".to_string()),
-        name: "BusType".to_string(),
-        value_type: AttrValueType::String,
-        ..Default::default()
-    };
-    db.attr_spec.insert("BusType".to_string(), bustype_spec);
-
-    db.attributes
-        .insert("BusType".to_string(), AttributeValue::Str(bustype_label));
+    db.set_bus_type(bustype.clone());
 
     // Fill in default Baudrate for Standard CAN and its definition
-    let baudrate_spec: AttributeSpec = AttributeSpec {
-        type_of_object: AttrObject::Database,
-        default: AttributeValue::Int(500_000),
-        name: "Baudrate".to_string(),
-        value_type: AttrValueType::Int,
-        int_min: Some(1),
-        int_max: Some(1_000_000),
-        ..Default::default()
-    };
-    db.attr_spec.insert("Baudrate".to_string(), baudrate_spec);
-
-    db.attributes
-        .insert("Baudrate".to_string(), AttributeValue::Int(500_000));
+    db.set_baudrate_attribute("Baudrate", 500_000);
 
     // Fill in default Baudrate for CANFD and its definition (only if BusType==CanFd)
     if bustype == BusType::CanFd {
-        let baudrate_canfd_spec: AttributeSpec = AttributeSpec {
-            type_of_object: AttrObject::Database,
-            default: AttributeValue::Int(500_000),
-            name: "BaudrateCANFD".to_string(),
-            value_type: AttrValueType::Int,
-            int_min: Some(1),
-            int_max: Some(16_000_000),
-            ..Default::default()
-        };
-        db.attr_spec
-            .insert("BaudrateCANFD".to_string(), baudrate_canfd_spec);
-
-        db.attributes
-            .insert("BaudrateCANFD".to_string(), AttributeValue::Int(2_000_000));
+        db.set_baudrate_attribute("BaudrateCANFD", 2_000_000);
     }
 
     // Take current time values
@@ -1,14 +1,16 @@
 use autosar_data::{AttributeName, AutosarModel, CharacterData, Element, ElementName, EnumItem};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 
 use encoding_rs::WINDOWS_1252;
 
 use crate::core;
+use crate::save::NS_KEYWORDS;
 use crate::types::{
-    database::{BusType, CanDatabase, CanMessageKey, CanNodeKey},
+    database::{BusType, CanDatabase, CanMessageKey, CanNodeKey, CanSignalKey},
     errors::{ArxmlConvertError, DatabaseError, DbcParseError},
-    message::MuxRole,
+    message::{FrameFormat, MuxRole, MuxSelector},
     signal::{Endianness, Signess},
 };
 
@@ -49,9 +51,62 @@ use crate::types::{
 /// # Notes
 /// - This function is the main entry point for converting a DBC file into a structured [`CanDatabase`].
 /// - Internal parsing details are handled by [`CanDatabase`] methods and are **not** part of the public API.
-/// - Parsing stops only at the end of the file; malformed lines are skipped.
+/// - Parsing stops only at the end of the file; malformed lines are skipped, and lines that
+///   do not start with a DBC keyword are reported in [`CanDatabase::parse_warnings`].
+/// - Environment variables are not modelled: `EV_` statements, their comments (`CM_ EV_`)
+///   and value descriptions (`VAL_ <EnvVarName> <value> "<desc>" ... ;`) are kept verbatim in
+///   [`CanDatabase::unparsed_lines`] and written back unchanged on save.
 ///
 pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
+    from_dbc_file_with_progress(path, |_, _| {})
+}
+
+/// Strict variant of [`from_dbc_file`]: fails with [`DbcParseError::MalformedLines`]
+/// listing the `(line number, line)` pairs that could not be decoded, instead of
+/// skipping them.
+///
+/// Malformed lines are currently those rejected by the `BO_` and `SG_` decoders
+/// (e.g. a non-numeric message id or a missing bit layout).
+pub fn from_dbc_file_strict(path: &str) -> Result<CanDatabase, DbcParseError> {
+    let file: File = open_dbc_file(path)?;
+    let mut malformed: Vec<(usize, String)> = Vec::new();
+    let db: CanDatabase = parse_reader(BufReader::new(file), path, &mut malformed)?;
+    if malformed.is_empty() {
+        Ok(db)
+    } else {
+        Err(DbcParseError::MalformedLines { lines: malformed })
+    }
+}
+
+/// Same as [`from_dbc_file`], calling `on_progress(bytes_read, total_bytes)` as the file
+/// is consumed, e.g. to drive a progress bar.
+///
+/// `total_bytes` is the file size; the last call reports `bytes_read == total_bytes`.
+pub fn from_dbc_file_with_progress(
+    path: &str,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<CanDatabase, DbcParseError> {
+    let file: File = open_dbc_file(path)?;
+
+    let total: u64 = file
+        .metadata()
+        .map_err(|source| DbcParseError::OpenFile {
+            path: path.to_string(),
+            source,
+        })?
+        .len();
+
+    let reader = ProgressReader {
+        inner: BufReader::new(file),
+        read: 0,
+        total,
+        on_progress,
+    };
+    parse_reader(reader, path, &mut Vec::new())
+}
+
+/// Opens `path` after checking it has the `.dbc` extension.
+fn open_dbc_file(path: &str) -> Result<File, DbcParseError> {
     // check if provided file has .dbc format
     if !path.to_lowercase().ends_with(".dbc") {
         return Err(DbcParseError::InvalidExtension {
@@ -59,128 +114,231 @@ pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
         });
     }
 
-    let path_owned: String = path.to_string();
-    let file: File = File::open(path).map_err(|source| DbcParseError::OpenFile {
-        path: path_owned.clone(),
+    File::open(path).map_err(|source| DbcParseError::OpenFile {
+        path: path.to_string(),
         source,
-    })?;
-    let mut reader: BufReader<File> = BufReader::new(file);
+    })
+}
 
-    // Initialize CanDatabase
-    let mut db: CanDatabase = CanDatabase::default();
+/// [`BufRead`] adapter reporting the consumed byte count to a callback.
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    total: u64,
+    on_progress: F,
+}
 
-    // Buffer for raw bytes of a line
-    let mut raw_line: Vec<u8> = Vec::with_capacity(256);
+impl<R: BufRead, F: FnMut(u64, u64)> io::Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n: usize = io::Read::read(&mut self.inner, buf)?;
+        self.advance(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead, F: FnMut(u64, u64)> BufRead for ProgressReader<R, F> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.advance(amt);
+    }
+}
 
-    // For each line, transform german characters in UTF-8 compatible characters
-    let read_decoded_line = |reader: &mut BufReader<File>,
-                             buf: &mut Vec<u8>|
-     -> Result<Option<String>, DbcParseError> {
-        buf.clear();
-        let read = reader
-            .read_until(b'\n', buf)
-            .map_err(|source| DbcParseError::Read {
-                path: path_owned.clone(),
-                source,
-            })?;
-        if read == 0 {
-            return Ok(None);
+impl<R, F: FnMut(u64, u64)> ProgressReader<R, F> {
+    fn advance(&mut self, amt: usize) {
+        if amt > 0 {
+            self.read += amt as u64;
+            (self.on_progress)(self.read, self.total);
         }
-        let (decoded, _, _) = WINDOWS_1252.decode(buf);
-        let decoded_ref: &str = decoded.as_ref();
-        let mut replaced: Option<String> = None;
-
-        for (idx, ch) in decoded_ref.char_indices() {
-            match ch {
-                'ü' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('u');
-                }
-                'ö' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('o');
-                }
-                'ä' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('a');
-                }
-                'ß' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('s');
-                    buf.push('s');
-                }
-                'Ü' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('U');
-                }
-                'Ö' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('O');
-                }
-                'Ä' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('A');
-                }
-                '¿' => {
-                    let buf = replaced.get_or_insert_with(|| {
-                        let mut s = String::with_capacity(decoded_ref.len());
-                        s.push_str(&decoded_ref[..idx]);
-                        s
-                    });
-                    buf.push('?');
-                }
-                _ => {
-                    if let Some(buf) = replaced.as_mut() {
-                        buf.push(ch);
-                    }
+    }
+}
+
+/// Parses DBC content from any [`BufRead`] source (memory buffers, streams,
+/// embedded assets such as `include_bytes!`) and returns a populated [`CanDatabase`].
+///
+/// Behaves exactly like [`from_dbc_file`] (Windows-1252 decoding, multi-line
+/// comment joining, tolerant line handling) minus the file handling.
+/// Read failures are reported with the path `<reader>`.
+pub fn from_reader<R: BufRead>(reader: R) -> Result<CanDatabase, DbcParseError> {
+    parse_reader(reader, "<reader>", &mut Vec::new())
+}
+
+/// Reads one line, decoding it as Windows-1252 and transliterating German characters
+/// into UTF-8 compatible fallbacks. Returns `Ok(None)` at end of input.
+///
+/// On the `first_line` a UTF-8 byte order mark is dropped; the rest of the line is still
+/// decoded as Windows-1252 like every other line.
+fn read_decoded_line<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    source_name: &str,
+    first_line: bool,
+) -> Result<Option<String>, DbcParseError> {
+    buf.clear();
+    let read = reader
+        .read_until(b'\n', buf)
+        .map_err(|source| DbcParseError::Read {
+            path: source_name.to_string(),
+            source,
+        })?;
+    if read == 0 {
+        return Ok(None);
+    }
+    let bytes: &[u8] = match buf.strip_prefix(b"\xEF\xBB\xBF") {
+        Some(rest) if first_line => rest,
+        _ => buf,
+    };
+    let (decoded, _) = WINDOWS_1252.decode_without_bom_handling(bytes);
+    let decoded_ref: &str = decoded.as_ref();
+    let mut replaced: Option<String> = None;
+
+    for (idx, ch) in decoded_ref.char_indices() {
+        match ch {
+            'ü' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('u');
+            }
+            'ö' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('o');
+            }
+            'ä' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('a');
+            }
+            'ß' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('s');
+                buf.push('s');
+            }
+            'Ü' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('U');
+            }
+            'Ö' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('O');
+            }
+            'Ä' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('A');
+            }
+            '¿' => {
+                let buf = replaced.get_or_insert_with(|| {
+                    let mut s = String::with_capacity(decoded_ref.len());
+                    s.push_str(&decoded_ref[..idx]);
+                    s
+                });
+                buf.push('?');
+            }
+            _ => {
+                if let Some(buf) = replaced.as_mut() {
+                    buf.push(ch);
                 }
             }
         }
+    }
 
-        let mut line = match replaced {
-            Some(s) => s,
-            None => decoded.into_owned(),
-        };
-        // trim trailing CR/LF to behave like .lines()
-        while line.ends_with(['\n', '\r']) {
-            line.pop();
-        }
-        Ok(Some(line))
+    let mut line = match replaced {
+        Some(s) => s,
+        None => decoded.into_owned(),
     };
+    // trim trailing CR/LF to behave like .lines()
+    while line.ends_with(['\n', '\r']) {
+        line.pop();
+    }
+    Ok(Some(line))
+}
+
+/// Returns `line` joined with the following lines (separated by `\n`) until it holds a
+/// complete quoted segment, as multi-line `CM_` comments require. `line_no` is advanced
+/// for every extra line read.
+fn read_quoted_statement<R: BufRead>(
+    reader: &mut R,
+    raw_line: &mut Vec<u8>,
+    source_name: &str,
+    line_no: &mut usize,
+    line: &str,
+) -> Result<String, DbcParseError> {
+    let mut full_line: String = line.to_string();
+    if !core::strings::has_complete_quoted_segment(&full_line) {
+        // Read subsequent lines until we close the quoted segment
+        while let Some(next) = read_decoded_line(reader, raw_line, source_name, false)? {
+            *line_no += 1;
+            full_line.push('\n');
+            full_line.push_str(next.trim_start());
+            if core::strings::has_complete_quoted_segment(&full_line) {
+                break;
+            }
+        }
+    }
+    Ok(full_line)
+}
+
+/// Returns `true` for the statement keywords of the DBC format, including the ones this
+/// crate does not model.
+fn is_dbc_keyword(token: &str) -> bool {
+    NS_KEYWORDS.contains(&token) || matches!(token, "EV_" | "SG_MUL_VAL_")
+}
+
+/// Core DBC parsing loop shared by [`from_dbc_file`] and [`from_reader`].
+///
+/// Lines a decoder flags as malformed are appended to `malformed` with their 1-based
+/// line number.
+fn parse_reader<R: BufRead>(
+    mut reader: R,
+    source_name: &str,
+    malformed: &mut Vec<(usize, String)>,
+) -> Result<CanDatabase, DbcParseError> {
+    // Initialize CanDatabase
+    let mut db: CanDatabase = CanDatabase::default();
+
+    // Buffer for raw bytes of a line
+    let mut raw_line: Vec<u8> = Vec::with_capacity(256);
+    // Inside the `NS_ :` block, whose indented lines only list keywords
+    let mut in_ns_block: bool = false;
+    // 1-based number of the last line read
+    let mut line_no: usize = 0;
+    // SIG_TYPE_REF_ lines, resolved once every signal is known
+    let mut signal_type_refs: Vec<String> = Vec::new();
 
     // Read and process each .dbc line
-    loop {
-        let Some(line) = read_decoded_line(&mut reader, &mut raw_line)? else {
-            break;
-        };
+    // a byte order mark can only precede the first line
+    while let Some(line) = read_decoded_line(&mut reader, &mut raw_line, source_name, line_no == 0)?
+    {
+        line_no += 1;
+        let start_line_no: usize = line_no;
 
         // Work on a trimmed-start slice to preserve inner spaces elsewhere
         let line_trimmed: &str = line.trim_start();
@@ -190,6 +348,13 @@ pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
             continue;
         }
 
+        if in_ns_block {
+            if line.starts_with([' ', '\t']) {
+                continue;
+            }
+            in_ns_block = false;
+        }
+
         // Extract first, second and third part from the line
         let mut parts = line_trimmed.split_ascii_whitespace();
         let first: &str = parts.next().unwrap_or("");
@@ -200,8 +365,14 @@ pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
             "VERSION" => {
                 core::version::decode(&mut db, line_trimmed);
             }
+            "NS_" | "NS_:" => {
+                in_ns_block = true;
+            }
+            "BS_:" | "BS_" => {
+                core::bs_::decode(&mut db, line_trimmed);
+            }
             // Some DBCs use "BU_:" while others use "BU_". Accept both.
-            "BU_:" => {
+            "BU_:" | "BU_" => {
                 core::bu_::decode(&mut db, line_trimmed);
             }
             "BO_" => {
@@ -214,39 +385,26 @@ pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
                 core::bo_tx_bu_::decode(&mut db, line_trimmed);
             }
             "CM_" => {
+                let full_comment_line: String = read_quoted_statement(
+                    &mut reader,
+                    &mut raw_line,
+                    source_name,
+                    &mut line_no,
+                    line_trimmed,
+                )?;
                 if second.starts_with('"') {
                     // Network/global comment: CM_ "…";
-                    core::comments::cm_::decode(&mut db, line_trimmed);
+                    core::comments::cm_::decode(&mut db, &full_comment_line);
                 } else if second == "BO_" {
-                    core::comments::cm_bo_::decode(&mut db, line_trimmed);
+                    core::comments::cm_bo_::decode(&mut db, &full_comment_line);
                 } else if second == "SG_" {
-                    // Accumulate multiline until the comment has two unescaped quotes
-                    let mut full_comment_line: String = line_trimmed.to_string();
-                    if !core::strings::has_complete_quoted_segment(&full_comment_line) {
-                        // Read subsequent lines until we close the quoted segment
-                        while let Some(next) = read_decoded_line(&mut reader, &mut raw_line)? {
-                            let next_trim = next.trim_start();
-                            full_comment_line.push('\n');
-                            full_comment_line.push_str(next_trim);
-                            if core::strings::has_complete_quoted_segment(&full_comment_line) {
-                                break;
-                            }
-                        }
-                    }
                     core::comments::cm_sg_::decode(&mut db, &full_comment_line);
                 } else if second == "BU_" {
-                    let mut full_comment_line: String = line_trimmed.to_string();
-                    if !core::strings::has_complete_quoted_segment(&full_comment_line) {
-                        while let Some(next) = read_decoded_line(&mut reader, &mut raw_line)? {
-                            let next_trim = next.trim_start();
-                            full_comment_line.push('\n');
-                            full_comment_line.push_str(next_trim);
-                            if core::strings::has_complete_quoted_segment(&full_comment_line) {
-                                break;
-                            }
-                        }
-                    }
                     core::comments::cm_bu_::decode(&mut db, &full_comment_line);
+                } else {
+                    // environment variable comments (CM_ EV_) are kept verbatim
+                    db.unparsed_lines
+                        .push(full_comment_line.trim_end().to_string());
                 }
             }
             "BA_DEF_" => {
@@ -286,11 +444,44 @@ pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
             "VAL_" => {
                 core::val_::decode(&mut db, line_trimmed);
             }
+            "VAL_TABLE_" => {
+                core::val_table_::decode(&mut db, line_trimmed);
+            }
             "SIG_VALTYPE_" => {
                 core::attributes::sig_valtype_::decode(&mut db, line_trimmed);
             }
-            _ => {}
+            // signal types are only read for their value tables and written back verbatim
+            "SGTYPE_" => {
+                core::sgtype_::decode(&mut db, line_trimmed);
+                db.unparsed_lines.push(line_trimmed.trim_end().to_string());
+            }
+            "SGTYPE_VAL_" => {
+                core::sgtype_::decode_val(&mut db, line_trimmed);
+                db.unparsed_lines.push(line_trimmed.trim_end().to_string());
+            }
+            "SIG_TYPE_REF_" => {
+                signal_type_refs.push(line_trimmed.to_string());
+                db.unparsed_lines.push(line_trimmed.trim_end().to_string());
+            }
+            // keep statements this crate does not model (SIG_GROUP_, EV_, CAT_, ...)
+            _ if is_dbc_keyword(first) => {
+                db.unparsed_lines.push(line_trimmed.trim_end().to_string())
+            }
+            _ => db.parse_warnings.push(format!(
+                "line {start_line_no}: ignored \"{}\", not a DBC statement",
+                line_trimmed.trim_end()
+            )),
         }
+
+        if db.line_malformed {
+            malformed.push((start_line_no, line_trimmed.trim_end().to_string()));
+            db.line_malformed = false;
+        }
+    }
+
+    // finalization: references that may precede the statements they point to
+    for line in &signal_type_refs {
+        core::sig_type_ref_::decode(&mut db, line);
     }
 
     // re-order
@@ -309,6 +500,15 @@ pub fn from_dbc_file(path: &str) -> Result<CanDatabase, DbcParseError> {
 /// defined `CAN-CLUSTER`s. Each cluster becomes its own database, populated with
 /// known messages, signals, and nodes derived from the frame ports.
 pub fn from_arxml_file(path: &str) -> Result<Vec<CanDatabase>, ArxmlConvertError> {
+    from_arxml_file_with_progress(path, |_, _| {})
+}
+
+/// Same as [`from_arxml_file`], calling `on_progress(clusters_done, clusters_total)` after
+/// each `CAN-CLUSTER` has been converted.
+pub fn from_arxml_file_with_progress(
+    path: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<Vec<CanDatabase>, ArxmlConvertError> {
     if !path.to_lowercase().ends_with(".arxml") {
         return Err(ArxmlConvertError::InvalidExtension {
             path: path.to_string(),
@@ -325,15 +525,17 @@ pub fn from_arxml_file(path: &str) -> Result<Vec<CanDatabase>, ArxmlConvertError
             source: io::Error::other(source),
         })?;
 
-    let mut databases: Vec<CanDatabase> = Vec::new();
-
-    for element in model
+    let clusters: Vec<Element> = model
         .identifiable_elements()
         .filter_map(|(_, weak)| weak.upgrade())
-    {
-        if element.element_name() == ElementName::CanCluster
-            && let Some(mut db) = build_can_database(&element)
-        {
+        .filter(|element| element.element_name() == ElementName::CanCluster)
+        .collect();
+    let total: u64 = clusters.len() as u64;
+
+    let mut databases: Vec<CanDatabase> = Vec::new();
+
+    for (done, element) in clusters.iter().enumerate() {
+        if let Some(mut db) = build_can_database(element) {
             // re-order
             CanDatabase::sort_attribute_map(&mut db.attributes);
             db.sort_db_nodes_by_name();
@@ -344,6 +546,7 @@ pub fn from_arxml_file(path: &str) -> Result<Vec<CanDatabase>, ArxmlConvertError
             db.sort_all_signal_fields();
             databases.push(db);
         }
+        on_progress(done as u64 + 1, total);
     }
 
     Ok(databases)
@@ -360,6 +563,18 @@ fn build_can_database(cluster: &Element) -> Option<CanDatabase> {
         .get_sub_element(ElementName::CanClusterVariants)
         .and_then(|ccv| ccv.get_sub_element(ElementName::CanClusterConditional))?;
 
+    let baudrate = |name: ElementName| {
+        ccc.get_sub_element(name)
+            .and_then(|elem| elem.character_data())
+            .and_then(|cdata| cdata.parse_integer::<u32>())
+    };
+    if let Some(baud) = baudrate(ElementName::Baudrate) {
+        db.set_baudrate_attribute("Baudrate", baud);
+    }
+    if let Some(baud) = baudrate(ElementName::CanFdBaudrate) {
+        db.set_baudrate_attribute("BaudrateCANFD", baud);
+    }
+
     if ccc
         .get_sub_element(ElementName::CanFdBaudrate)
         .and_then(|elem| elem.character_data())
@@ -411,6 +626,10 @@ fn process_can_frame_triggering(db: &mut CanDatabase, frame_triggering: &Element
         .unwrap_or(0);
 
     let msg_key: CanMessageKey = ensure_message(db, &frame_name, can_id, byte_length);
+    let frame_format: FrameFormat = frame_format_of(frame_triggering, byte_length);
+    if let Some(message) = db.get_message_by_key_mut(msg_key) {
+        message.set_frame_format(frame_format);
+    }
 
     // Sender/receiver nodes
     let frame_ports: Vec<Element> = frame_triggering
@@ -422,6 +641,7 @@ fn process_can_frame_triggering(db: &mut CanDatabase, frame_triggering: &Element
                 .collect()
         })
         .unwrap_or_default();
+    copy_ecu_descriptions(db, &frame_ports);
     let (sender_ecus, receiver_ecus) = get_rx_tx_ecus(frame_ports);
     for ecu in sender_ecus {
         if let Some(nk) = ensure_node(db, &ecu) {
@@ -442,6 +662,34 @@ fn process_can_frame_triggering(db: &mut CanDatabase, frame_triggering: &Element
     }
 }
 
+/// Derives the frame format of a `<CAN-FRAME-TRIGGERING>`.
+///
+/// A frame is CAN FD when its TX behavior is `CAN-FD`, when it declares
+/// `<CAN-FD-FRAME-SUPPORT>`, or when its payload does not fit a classic frame.
+fn frame_format_of(frame_triggering: &Element, byte_length: u16) -> FrameFormat {
+    let text = |name: ElementName| {
+        frame_triggering
+            .get_sub_element(name)
+            .and_then(|elem| elem.character_data())
+            .map(|cdata| cdata.to_string().trim().to_ascii_uppercase())
+    };
+
+    let is_fd: bool = text(ElementName::CanFrameTxBehavior).as_deref() == Some("CAN-FD")
+        || matches!(
+            text(ElementName::CanFdFrameSupport).as_deref(),
+            Some("TRUE" | "1")
+        )
+        || byte_length > 8;
+    let is_extended: bool = text(ElementName::CanAddressingMode).as_deref() == Some("EXTENDED");
+
+    match (is_fd, is_extended) {
+        (false, false) => FrameFormat::StandardCan,
+        (false, true) => FrameFormat::ExtendedCan,
+        (true, false) => FrameFormat::StandardCanFd,
+        (true, true) => FrameFormat::ExtendedCanFd,
+    }
+}
+
 /// Converte un `<I-SIGNAL-I-PDU>` (o contenitori annidati) in segnali DBC.
 fn collect_isignal_mappings(
     db: &mut CanDatabase,
@@ -457,18 +705,119 @@ fn collect_isignal_mappings(
 
     if pdu.element_name() == ElementName::ISignalIPdu || pdu.element_name() == ElementName::NmPdu {
         // NM-PDU condivide la stessa struttura di mapping degli I-SIGNAL-I-PDU
-        process_isignal_ipdu(db, msg_key, pdu, receiver_ecus);
+        process_isignal_ipdu(db, msg_key, pdu, receiver_ecus, None);
+    } else if pdu.element_name() == ElementName::MultiplexedIPdu {
+        process_multiplexed_ipdu(db, msg_key, pdu, receiver_ecus);
     } else if pdu.element_name() == ElementName::NPdu {
         process_npdu(db, msg_key, pdu);
     }
 }
 
+/// Converts a `MULTIPLEXED-I-PDU`: the selector field becomes the multiplexor signal,
+/// the static part signals are plain, and the signals of each dynamic part alternative
+/// are multiplexed on its `<SELECTOR-FIELD-CODE>`.
+fn process_multiplexed_ipdu(
+    db: &mut CanDatabase,
+    msg_key: CanMessageKey,
+    pdu: &Element,
+    receiver_ecus: &[String],
+) {
+    let number = |name: ElementName| {
+        pdu.get_sub_element(name)
+            .and_then(|elem| elem.character_data())
+            .and_then(|cdata| cdata.parse_integer::<u16>())
+    };
+
+    // 1) selector field as multiplexor
+    if let Some(selector_length) = number(ElementName::SelectorFieldLength) {
+        let selector_start: u16 = number(ElementName::SelectorFieldStartPosition).unwrap_or(0);
+        let endian: Endianness = byte_order_of(pdu, ElementName::SelectorFieldByteOrder);
+        let max: f64 = ((1u64 << selector_length.min(63)) - 1) as f64;
+        let name: String = format!("{}_Selector", pdu.item_name().unwrap_or_default());
+        let sig_key: CanSignalKey =
+            db.add_signal_unchecked(&name, endian, Signess::Unsigned, 1.0, 0.0, 0.0, max, "");
+        if let Some(signal) = db.get_sig_by_key_mut(sig_key) {
+            signal.bit_start = selector_start;
+            signal.bit_length = selector_length;
+            signal.steps.clear();
+            signal.compile_inline();
+        }
+        if db
+            .add_msg_sig_relation_unchecked(sig_key, msg_key, MuxRole::Multiplexor, None)
+            .is_ok()
+        {
+            for ecu in receiver_ecus {
+                if let Some(nk) = ensure_node(db, ecu) {
+                    let _ = db.add_sig_receiver_node(sig_key, nk);
+                }
+            }
+        }
+    }
+
+    // 2) static part (older schemas nest a single STATIC-PART directly)
+    let static_parts: Vec<Element> = match pdu.get_sub_element(ElementName::StaticParts) {
+        Some(parts) => parts.sub_elements().collect(),
+        None => pdu
+            .get_sub_element(ElementName::StaticPart)
+            .into_iter()
+            .collect(),
+    };
+    for part in static_parts {
+        if let Some(static_pdu) = referenced(&part, ElementName::IPduRef) {
+            process_isignal_ipdu(db, msg_key, &static_pdu, receiver_ecus, None);
+        }
+    }
+
+    // 3) dynamic part alternatives
+    let Some(dynamic_parts) = pdu.get_sub_element(ElementName::DynamicParts) else {
+        return;
+    };
+    for alternative in dynamic_parts
+        .sub_elements()
+        .filter_map(|part| part.get_sub_element(ElementName::DynamicPartAlternatives))
+        .flat_map(|alternatives| alternatives.sub_elements())
+    {
+        let Some(code) = alternative
+            .get_sub_element(ElementName::SelectorFieldCode)
+            .and_then(|elem| elem.character_data())
+            .and_then(|cdata| cdata.parse_integer::<u32>())
+        else {
+            continue;
+        };
+        if let Some(dynamic_pdu) = referenced(&alternative, ElementName::IPduRef) {
+            process_isignal_ipdu(
+                db,
+                msg_key,
+                &dynamic_pdu,
+                receiver_ecus,
+                Some(MuxSelector::Value(code)),
+            );
+        }
+    }
+}
+
+/// Reads a byte order element of `elem` (Motorola when absent).
+fn byte_order_of(elem: &Element, name: ElementName) -> Endianness {
+    match elem
+        .get_sub_element(name)
+        .and_then(|elem| elem.character_data())
+    {
+        Some(CharacterData::Enum(EnumItem::MostSignificantByteFirst)) => Endianness::Motorola,
+        Some(CharacterData::Enum(EnumItem::MostSignificantByteLast)) => Endianness::Intel,
+        Some(CharacterData::Enum(EnumItem::Opaque)) => Endianness::Intel, // treat opaque byte order as linear/Intel for fitting check
+        _ => Endianness::Motorola,
+    }
+}
+
 /// Processa un `I-SIGNAL-I-PDU` (o NM-PDU) convertendo i mapping in segnali DBC.
+///
+/// With a `selector`, the signals are multiplexed on it (dynamic part of a `MULTIPLEXED-I-PDU`).
 fn process_isignal_ipdu(
     db: &mut CanDatabase,
     msg_key: CanMessageKey,
     pdu: &Element,
     receiver_ecus: &[String],
+    selector: Option<MuxSelector>,
 ) {
     let Some(mappings) = pdu
         .get_sub_element(ElementName::ISignalToPduMappings)
@@ -496,43 +845,53 @@ fn process_isignal_ipdu(
             .and_then(|elem| elem.character_data())
             .and_then(|cdata| cdata.parse_integer::<u16>())
             .unwrap_or(0);
-        let endian: Endianness = match mapping
-            .get_sub_element(ElementName::PackingByteOrder)
-            .and_then(|elem| elem.character_data())
-        {
-            Some(CharacterData::Enum(EnumItem::MostSignificantByteFirst)) => Endianness::Motorola,
-            Some(CharacterData::Enum(EnumItem::MostSignificantByteLast)) => Endianness::Intel,
-            Some(CharacterData::Enum(EnumItem::Opaque)) => Endianness::Intel, // treat opaque byte order as linear/Intel for fitting check
-            _ => Endianness::Motorola,
-        };
+        let endian: Endianness = byte_order_of(&mapping, ElementName::PackingByteOrder);
 
-        let min: f64 = 0.0;
-        let mut max: f64 = 0.0;
-        if bit_length > 0 {
-            // intervallo massimo assumendo segnale unsigned
-            let max_raw: u64 = if bit_length < 64 {
-                (1u64 << bit_length) - 1
-            } else {
-                u64::MAX
-            };
-            max = max_raw as f64;
-        }
+        let scaling: SignalScaling = signal_scaling(&signal_elem);
+
+        // physical range of the raw span, assuming an unsigned signal
+        let max_raw: f64 = if bit_length == 0 {
+            0.0
+        } else if bit_length < 64 {
+            ((1u64 << bit_length) - 1) as f64
+        } else {
+            u64::MAX as f64
+        };
+        let low: f64 = scaling.offset;
+        let high: f64 = max_raw * scaling.factor + scaling.offset;
+        let min: f64 = scaling.min.unwrap_or(low.min(high));
+        let max: f64 = scaling.max.unwrap_or(low.max(high));
 
         let comment: Option<String> = extract_desc(&signal_elem);
 
-        let sig_key = db.add_signal(&sig_name, endian, Signess::Unsigned, 1.0, 0.0, min, max, "");
+        let sig_key = db.add_signal_unchecked(
+            &sig_name,
+            endian,
+            Signess::Unsigned,
+            scaling.factor,
+            scaling.offset,
+            min,
+            max,
+            &scaling.unit,
+        );
         if let Some(signal) = db.get_sig_by_key_mut(sig_key) {
             signal.bit_start = bit_start;
             signal.bit_length = bit_length;
             if let Some(desc) = comment {
                 signal.comment = desc;
             }
+            signal.value_table = scaling.value_table;
             signal.steps.clear();
             signal.compile_inline();
         }
 
+        let mux_role: MuxRole = if selector.is_some() {
+            MuxRole::Multiplexed
+        } else {
+            MuxRole::None
+        };
         if db
-            .add_msg_sig_relation(sig_key, msg_key, MuxRole::None, None)
+            .add_msg_sig_relation_unchecked(sig_key, msg_key, mux_role, selector.clone())
             .is_ok()
         {
             for ecu in receiver_ecus {
@@ -544,6 +903,162 @@ fn process_isignal_ipdu(
     }
 }
 
+/// Linear scaling, unit, physical limits and value table of an `<I-SIGNAL>`.
+struct SignalScaling {
+    factor: f64,
+    offset: f64,
+    unit: String,
+    min: Option<f64>,
+    max: Option<f64>,
+    value_table: BTreeMap<i32, String>,
+}
+
+/// Resolves the scaling of an `<I-SIGNAL>` from its compu-method (network representation
+/// first, then the `<SYSTEM-SIGNAL>` physical props), its unit and its data constraint.
+/// TEXTTABLE scales of the compu-method become the value table.
+///
+/// Missing pieces fall back to `factor = 1`, `offset = 0`, no unit and no limits.
+fn signal_scaling(signal_elem: &Element) -> SignalScaling {
+    let mut scaling: SignalScaling = SignalScaling {
+        factor: 1.0,
+        offset: 0.0,
+        unit: String::new(),
+        min: None,
+        max: None,
+        value_table: BTreeMap::new(),
+    };
+
+    let network_props: Option<Element> =
+        sw_data_def_props(signal_elem, ElementName::NetworkRepresentationProps);
+    let physical_props: Option<Element> = signal_elem
+        .get_sub_element(ElementName::SystemSignalRef)
+        .and_then(|elem| elem.get_reference_target().ok())
+        .and_then(|system_signal| sw_data_def_props(&system_signal, ElementName::PhysicalProps));
+    let props: Vec<&Element> = network_props.iter().chain(physical_props.iter()).collect();
+
+    let compu_method: Option<Element> = props
+        .iter()
+        .find_map(|p| referenced(p, ElementName::CompuMethodRef));
+
+    let compu_scales: Vec<Element> = compu_method
+        .as_ref()
+        .and_then(|cm| cm.get_sub_element(ElementName::CompuInternalToPhys))
+        .and_then(|itp| itp.get_sub_element(ElementName::CompuScales))
+        .map(|scales| scales.sub_elements().collect())
+        .unwrap_or_default();
+
+    // TEXTTABLE entries: a `<COMPU-CONST>` text for the raw value in `<LOWER-LIMIT>`
+    for scale in &compu_scales {
+        if let Some(text) = scale
+            .get_sub_element(ElementName::CompuConst)
+            .and_then(|cc| cc.get_sub_element(ElementName::Vt))
+            .and_then(|vt| vt.character_data())
+            .and_then(text_from_cdata)
+            && let Some(raw) = limit_value(scale, ElementName::LowerLimit)
+        {
+            scaling.value_table.insert(raw as i32, text);
+        }
+    }
+
+    // linear scaling: phys = (n0 + n1 * raw) / d0
+    if let Some(coeffs) = compu_scales
+        .iter()
+        .find_map(|scale| scale.get_sub_element(ElementName::CompuRationalCoeffs))
+    {
+        let numerator: Vec<f64> = coefficients(&coeffs, ElementName::CompuNumerator);
+        let denominator: f64 = coefficients(&coeffs, ElementName::CompuDenominator)
+            .first()
+            .copied()
+            .filter(|d| *d != 0.0)
+            .unwrap_or(1.0);
+        scaling.offset = numerator.first().copied().unwrap_or(0.0) / denominator;
+        scaling.factor = numerator.get(1).copied().unwrap_or(1.0) / denominator;
+    }
+
+    // unit: compu-method first, then the physical props
+    let unit: Option<Element> = compu_method
+        .as_ref()
+        .and_then(|cm| referenced(cm, ElementName::UnitRef))
+        .or_else(|| {
+            props
+                .iter()
+                .find_map(|p| referenced(p, ElementName::UnitRef))
+        });
+    if let Some(unit) = unit {
+        scaling.unit = unit
+            .get_sub_element(ElementName::DisplayName)
+            .and_then(|elem| elem.character_data())
+            .and_then(text_from_cdata)
+            .or_else(|| unit.item_name())
+            .unwrap_or_default();
+    }
+
+    // limits: physical constraints as-is, internal constraints scaled
+    if let Some(rule) = props
+        .iter()
+        .find_map(|p| referenced(p, ElementName::DataConstrRef))
+        .and_then(|dc| dc.get_sub_element(ElementName::DataConstrRules))
+        .and_then(|rules| rules.sub_elements().next())
+    {
+        if let Some(phys) = rule.get_sub_element(ElementName::PhysConstrs) {
+            scaling.min = limit_value(&phys, ElementName::LowerLimit);
+            scaling.max = limit_value(&phys, ElementName::UpperLimit);
+        } else if let Some(internal) = rule.get_sub_element(ElementName::InternalConstrs) {
+            let to_phys = |raw: f64| raw * scaling.factor + scaling.offset;
+            let low: Option<f64> = limit_value(&internal, ElementName::LowerLimit).map(to_phys);
+            let high: Option<f64> = limit_value(&internal, ElementName::UpperLimit).map(to_phys);
+            if let (Some(low), Some(high)) = (low, high) {
+                scaling.min = Some(low.min(high));
+                scaling.max = Some(low.max(high));
+            }
+        }
+    }
+
+    scaling
+}
+
+/// Returns the `<SW-DATA-DEF-PROPS-CONDITIONAL>` below the `container` child of `elem`.
+fn sw_data_def_props(elem: &Element, container: ElementName) -> Option<Element> {
+    elem.get_sub_element(container)?
+        .get_sub_element(ElementName::SwDataDefPropsVariants)?
+        .get_sub_element(ElementName::SwDataDefPropsConditional)
+}
+
+/// Follows the `reference` child of `elem` to its target.
+fn referenced(elem: &Element, reference: ElementName) -> Option<Element> {
+    elem.get_sub_element(reference)?.get_reference_target().ok()
+}
+
+/// Reads the `<V>` values of a `<COMPU-NUMERATOR>`/`<COMPU-DENOMINATOR>`.
+fn coefficients(coeffs: &Element, kind: ElementName) -> Vec<f64> {
+    coeffs
+        .get_sub_element(kind)
+        .map(|elem| {
+            elem.sub_elements()
+                .filter(|se| se.element_name() == ElementName::V)
+                .filter_map(|v| {
+                    v.character_data()
+                        .and_then(|cdata| number_from_cdata(&cdata))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads a numeric `<LOWER-LIMIT>`/`<UPPER-LIMIT>` (`INF` and friends are ignored).
+fn limit_value(constrs: &Element, limit: ElementName) -> Option<f64> {
+    constrs
+        .get_sub_element(limit)?
+        .character_data()
+        .and_then(|cdata| number_from_cdata(&cdata))
+        .filter(|v| v.is_finite())
+}
+
+/// Parses numeric character data, whatever its stored representation.
+fn number_from_cdata(cdata: &CharacterData) -> Option<f64> {
+    cdata.to_string().trim().parse::<f64>().ok()
+}
+
 /// Ricava le ECU trasmettenti/riceventi dai `<FRAME-PORT-REF>`.
 fn get_rx_tx_ecus(frame_ports: Vec<Element>) -> (Vec<String>, Vec<String>) {
     let cap = frame_ports.len();
@@ -574,11 +1089,32 @@ fn get_rx_tx_ecus(frame_ports: Vec<Element>) -> (Vec<String>, Vec<String>) {
 
 /// Risale l'arborescenza del frame port per ottenere il nome dell'ECU.
 fn ecu_of_frame_port(frame_port: &Element) -> Option<String> {
+    ecu_instance_of_frame_port(frame_port)?.item_name()
+}
+
+/// Returns the `<ECU-INSTANCE>` owning a frame port.
+fn ecu_instance_of_frame_port(frame_port: &Element) -> Option<Element> {
     let ecu_comm_port_instance = frame_port.parent().ok()??;
     let comm_connector = ecu_comm_port_instance.parent().ok()??;
     let connectors = comm_connector.parent().ok()??;
-    let ecu_instance = connectors.parent().ok()??;
-    ecu_instance.item_name()
+    connectors.parent().ok()?
+}
+
+/// Copies the `<DESC>` of the ECUs behind `frame_ports` into the comment of their
+/// nodes, leaving comments already set untouched.
+fn copy_ecu_descriptions(db: &mut CanDatabase, frame_ports: &[Element]) {
+    for ecu_instance in frame_ports.iter().filter_map(ecu_instance_of_frame_port) {
+        let (Some(name), Some(desc)) = (ecu_instance.item_name(), extract_desc(&ecu_instance))
+        else {
+            continue;
+        };
+        if let Some(nk) = ensure_node(db, &name)
+            && let Some(node) = db.get_node_by_key_mut(nk)
+            && node.comment.is_empty()
+        {
+            node.comment = desc;
+        }
+    }
 }
 
 /// Ensures a node with the given name exists, returning its key.
@@ -610,11 +1146,11 @@ fn ensure_message(db: &mut CanDatabase, name: &str, id: u32, dlc: u16) -> CanMes
         return k;
     }
 
-    match db.add_message(name, id, dlc) {
+    match db.add_message_unchecked(name, id, dlc) {
         Ok(k) => k,
         Err(_) => {
             let fallback_name = format!("{name}_{id}");
-            db.add_message(&fallback_name, id, dlc)
+            db.add_message_unchecked(&fallback_name, id, dlc)
                 .expect("fallback message creation failed")
         }
     }
@@ -727,7 +1263,7 @@ fn process_npdu(db: &mut CanDatabase, msg_key: CanMessageKey, pdu: &Element) {
         u64::MAX as f64
     };
 
-    let sig_key = db.add_signal(
+    let sig_key = db.add_signal_unchecked(
         &msg_name,
         Endianness::Intel,
         Signess::Unsigned,
@@ -744,5 +1280,5 @@ fn process_npdu(db: &mut CanDatabase, msg_key: CanMessageKey, pdu: &Element) {
         signal.compile_inline();
     }
 
-    let _ = db.add_msg_sig_relation(sig_key, msg_key, MuxRole::None, None);
+    let _ = db.add_msg_sig_relation_unchecked(sig_key, msg_key, MuxRole::None, None);
 }
@@ -5,16 +5,19 @@ use std::fs::{self, File};
 use std::io::{self, BufWriter, Write};
 use std::path::Path;
 
+use crate::core::strings::format_f64;
 use crate::types::attributes::AttrObject;
 use crate::types::{
     attributes::{AttrValueType, AttributeSpec, AttributeValue},
-    database::{CanDatabase, CanSignalKey},
+    database::{BusType, CanDatabase, CanSignalKey},
     errors::DbcSaveError,
-    message::{MuxRole, MuxSelector},
+    message::{CanMessage, MuxRole, MuxSelector},
+    node::PLACEHOLDER_NODE,
     signal::{Endianness, Signess},
 };
 
-const NS_KEYWORDS: &[&str] = &[
+/// Keywords listed in the `NS_` block, which are also the statement keywords of a DBC.
+pub(crate) const NS_KEYWORDS: &[&str] = &[
     "NS_DESC_",
     "CM_",
     "BA_DEF_",
@@ -44,16 +47,138 @@ const NS_KEYWORDS: &[&str] = &[
     "BU_BO_REL_",
 ];
 
+const FRAME_FORMAT_ENUM: &[&str] = &[
+    "StandardCAN",
+    "ExtendedCAN",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "StandardCAN_FD",
+    "ExtendedCAN_FD",
+];
+const SEND_TYPE_ENUM: &[&str] = &[
+    "Cyclic",
+    "NotUsed",
+    "NotUsed",
+    "NotUsed",
+    "NotUsed",
+    "NotUsed",
+    "NotUsed",
+    "IfActive",
+    "NoMsgSendType",
+];
+
+/// Message attribute mirrored by a typed `CanMessage` field.
+///
+/// When set, the typed field wins over the raw attribute value; if the attribute is not
+/// defined in the database, `definition`/`default` are emitted so the file round-trips.
+struct TypedMessageAttribute {
+    name: &'static str,
+    definition: fn() -> String,
+    default: &'static str,
+    value: fn(&CanMessage, Option<&AttributeSpec>) -> Option<String>,
+}
+
+const TYPED_MESSAGE_ATTRIBUTES: &[TypedMessageAttribute] = &[
+    TypedMessageAttribute {
+        name: "VFrameFormat",
+        definition: || format_enum_definition(FRAME_FORMAT_ENUM),
+        default: "\"StandardCAN\"",
+        value: |message, _| message.frame_format.map(|f| f.code().to_string()),
+    },
+    TypedMessageAttribute {
+        name: "GenMsgSendType",
+        definition: || format_enum_definition(SEND_TYPE_ENUM),
+        default: "\"Cyclic\"",
+        value: |message, spec| {
+            let send_type: &String = message.send_type.as_ref()?;
+            Some(match spec {
+                Some(spec) => {
+                    format_attribute_value(&AttributeValue::Enum(send_type.clone()), Some(spec))
+                }
+                None => match SEND_TYPE_ENUM.iter().position(|e| e == send_type) {
+                    Some(idx) => idx.to_string(),
+                    None => format!("\"{}\"", escape_dbc_string(send_type)),
+                },
+            })
+        },
+    },
+    TypedMessageAttribute {
+        name: "GenMsgDelayTime",
+        definition: || "INT 0 65535".to_string(),
+        default: "0",
+        value: |message, _| message.delay_time.map(|d| d.to_string()),
+    },
+    TypedMessageAttribute {
+        name: "GenMsgCycleTime",
+        definition: || "INT 0 65535".to_string(),
+        default: "0",
+        value: |message, _| message.cycle_time.map(|c| c.to_string()),
+    },
+    TypedMessageAttribute {
+        name: "GenMsgStartDelayTime",
+        definition: || "INT 0 65535".to_string(),
+        default: "0",
+        value: |message, _| message.start_delay_time.map(|d| d.to_string()),
+    },
+    TypedMessageAttribute {
+        name: "GenMsgNrOfRepetition",
+        definition: || "INT 0 999999".to_string(),
+        default: "0",
+        value: |message, _| message.nr_of_repetition.map(|n| n.to_string()),
+    },
+];
 const AUTONET_FAKE_NODE: &str = "AutoNet_XXX";
 const AUTONET_FAKE_MSG_NAME: &str = "AUTONET__INDEPENDENT_SIG_MSG";
 const AUTONET_FAKE_MSG_ID: u32 = 3_221_225_479;
 
+/// Options controlling how a `CanDatabase` is serialized to DBC text.
+///
+/// The [`Default`] value reproduces the output of [`save_to_file`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveOptions {
+    /// Node name written where a message has no transmitter or a signal has no receivers.
+    pub empty_node_placeholder: String,
+    /// Node used as transmitter/receiver of the synthetic message holding independent signals.
+    pub independent_signal_node: String,
+    /// Whether the `NS_` block lists the new-symbol keywords (an empty `NS_ :` is written otherwise).
+    pub emit_ns_block: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        SaveOptions {
+            empty_node_placeholder: PLACEHOLDER_NODE.to_string(),
+            independent_signal_node: AUTONET_FAKE_NODE.to_string(),
+            emit_ns_block: true,
+        }
+    }
+}
+
 /// Serializes a `CanDatabase` into DBC text and writes it to `path`.
 ///
 /// Ensures the destination has a `.dbc` extension, creates intermediate
 /// directories when needed, and reports structured `DbcSaveError` variants
 /// for path, I/O, or formatting failures.
 pub fn save_to_file(path: &str, database: &CanDatabase) -> Result<(), DbcSaveError> {
+    save_to_file_with(path, database, &SaveOptions::default())
+}
+
+/// Same as [`save_to_file`], with custom [`SaveOptions`].
+pub fn save_to_file_with(
+    path: &str,
+    database: &CanDatabase,
+    options: &SaveOptions,
+) -> Result<(), DbcSaveError> {
     if !path.to_ascii_lowercase().ends_with(".dbc") {
         return Err(DbcSaveError::InvalidExtension {
             path: path.to_string(),
@@ -75,7 +200,7 @@ pub fn save_to_file(path: &str, database: &CanDatabase) -> Result<(), DbcSaveErr
         source,
     })?;
     let mut writer = BufWriter::new(file);
-    serialize_database(database, &mut writer).map_err(|source| DbcSaveError::Write {
+    serialize_database(database, options, &mut writer).map_err(|source| DbcSaveError::Write {
         path: path.to_string(),
         source,
     })?;
@@ -86,18 +211,61 @@ pub fn save_to_file(path: &str, database: &CanDatabase) -> Result<(), DbcSaveErr
     Ok(())
 }
 
+/// Serializes a `CanDatabase` into DBC text and returns it, without touching the filesystem.
+pub fn to_dbc_string(database: &CanDatabase) -> Result<String, DbcSaveError> {
+    to_dbc_string_with(database, &SaveOptions::default())
+}
+
+/// Same as [`to_dbc_string`], with custom [`SaveOptions`].
+pub fn to_dbc_string_with(
+    database: &CanDatabase,
+    options: &SaveOptions,
+) -> Result<String, DbcSaveError> {
+    let mut buffer: Vec<u8> = Vec::new();
+    if let Err(source) = serialize_database(database, options, &mut buffer) {
+        return Err(DbcSaveError::Format {
+            context: last_line(&buffer),
+            source,
+        });
+    }
+    String::from_utf8(buffer).map_err(|err| {
+        let valid_up_to: usize = err.utf8_error().valid_up_to();
+        DbcSaveError::Format {
+            context: last_line(&err.as_bytes()[..valid_up_to]),
+            source: io::Error::new(io::ErrorKind::InvalidData, err.utf8_error()),
+        }
+    })
+}
+
+/// Returns the text after the last newline of `buffer`, or the last complete line when
+/// the buffer ends with one.
+fn last_line(buffer: &[u8]) -> String {
+    let text = String::from_utf8_lossy(buffer);
+    let text: &str = text.trim_end_matches('\n');
+    text.rsplit('\n').next().unwrap_or_default().to_string()
+}
+
 /// Serializes the database into raw DBC text using the provided writer.
-fn serialize_database<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
+fn serialize_database<W: Write>(
+    db: &CanDatabase,
+    options: &SaveOptions,
+    out: &mut W,
+) -> io::Result<()> {
     let version = escape_dbc_string(&db.version);
     write_fmt(out, format_args!("VERSION \"{}\"\n\n", version))?;
 
     write_fmt(out, format_args!("NS_ :\n"))?;
-    for keyword in NS_KEYWORDS {
-        write_fmt(out, format_args!("\t{}\n", keyword))?;
+    if options.emit_ns_block {
+        for keyword in NS_KEYWORDS {
+            write_fmt(out, format_args!("\t{}\n", keyword))?;
+        }
     }
     write_fmt(out, format_args!("\n"))?;
 
-    write_fmt(out, format_args!("BS_:\n\n"))?;
+    match db.bit_timing {
+        Some(bit_timing) => write_fmt(out, format_args!("BS_: {}\n\n", bit_timing))?,
+        None => write_fmt(out, format_args!("BS_:\n\n"))?,
+    }
 
     write_fmt(out, format_args!("BU_:"))?;
     for node in db.iter_nodes() {
@@ -105,11 +273,16 @@ fn serialize_database<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()>
     }
     write_fmt(out, format_args!("\n\n"))?;
 
+    if !db.value_tables.is_empty() {
+        write_global_value_tables(db, out)?;
+        write_fmt(out, format_args!("\n"))?;
+    }
+
     let independent: Vec<CanSignalKey> = collect_independent_signals(db);
-    write_independent_signals_as_fake_message(db, &independent, out)?;
+    write_independent_signals_as_fake_message(db, options, &independent, out)?;
     write_fmt(out, format_args!("\n"))?;
 
-    write_messages(db, out)?;
+    write_messages(db, options, out)?;
     write_fmt(out, format_args!("\n"))?;
 
     write_bo_tx_bu(db, out)?;
@@ -137,17 +310,34 @@ fn serialize_database<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()>
     write_sig_valtype(db, out)?;
     write_value_tables(db, out)?;
 
+    if !db.unparsed_lines.is_empty() {
+        write_fmt(out, format_args!("\n"))?;
+        write_unparsed_lines(db, out)?;
+    }
+
+    Ok(())
+}
+
+/// Re-emits the statements the parser kept verbatim in `unparsed_lines`.
+fn write_unparsed_lines<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
+    for line in &db.unparsed_lines {
+        write_fmt(out, format_args!("{}\n", line))?;
+    }
     Ok(())
 }
 
 /// Writes each message and its signals into standard DBC syntax.
-fn write_messages<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
+fn write_messages<W: Write>(
+    db: &CanDatabase,
+    options: &SaveOptions,
+    out: &mut W,
+) -> io::Result<()> {
     for message in db.iter_messages() {
         let transmitter = message
             .sender_nodes
             .iter()
             .find_map(|nk| db.get_node_by_key(*nk).map(|node| node.name.as_str()))
-            .unwrap_or("Vector__XXX");
+            .unwrap_or(&options.empty_node_placeholder);
 
         write_fmt(
             out,
@@ -180,7 +370,7 @@ fn write_messages<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
                     .filter_map(|nk| db.get_node_by_key(*nk).map(|node| node.name.as_str()))
                     .collect();
                 let receivers_field = if receivers.is_empty() {
-                    "Vector__XXX".to_string()
+                    options.empty_node_placeholder.clone()
                 } else {
                     receivers.join(",")
                 };
@@ -250,20 +440,34 @@ fn write_attribute_definitions<W: Write>(db: &CanDatabase, out: &mut W) -> io::R
         let signature = format_attribute_spec(spec);
         match spec.type_of_object {
             AttrObject::Database => {
-                let _ = write!(db_defs, "BA_DEF_ \"{}\" {};\n", name, signature);
+                let _ = writeln!(db_defs, "BA_DEF_ \"{}\" {};", name, signature);
             }
             AttrObject::Node => {
-                let _ = write!(bu_defs, "BA_DEF_ BU_ \"{}\" {};\n", name, signature);
+                let _ = writeln!(bu_defs, "BA_DEF_ BU_ \"{}\" {};", name, signature);
             }
             AttrObject::Message => {
-                let _ = write!(bo_defs, "BA_DEF_ BO_ \"{}\" {};\n", name, signature);
+                let _ = writeln!(bo_defs, "BA_DEF_ BO_ \"{}\" {};", name, signature);
             }
             AttrObject::Signal => {
-                let _ = write!(sg_defs, "BA_DEF_ SG_ \"{}\" {};\n", name, signature);
+                let _ = writeln!(sg_defs, "BA_DEF_ SG_ \"{}\" {};", name, signature);
             }
         }
     }
 
+    if bus_type_needs_definition(db) {
+        let _ = writeln!(db_defs, "BA_DEF_ \"BusType\" STRING;");
+    }
+
+    // Typed message fields set without a matching definition still need one to round-trip
+    for typed in missing_typed_message_attributes(db) {
+        let _ = writeln!(
+            bo_defs,
+            "BA_DEF_ BO_ \"{}\" {};",
+            typed.name,
+            (typed.definition)()
+        );
+    }
+
     out.write_all(db_defs.as_bytes())?;
     out.write_all(bu_defs.as_bytes())?;
     out.write_all(bo_defs.as_bytes())?;
@@ -310,6 +514,17 @@ fn write_attribute_defaults<W: Write>(db: &CanDatabase, out: &mut W) -> io::Resu
         )?;
     }
 
+    if bus_type_needs_definition(db) {
+        write_fmt(out, format_args!("BA_DEF_DEF_ \"BusType\" \"\";\n"))?;
+    }
+
+    for typed in missing_typed_message_attributes(db) {
+        write_fmt(
+            out,
+            format_args!("BA_DEF_DEF_ \"{}\" {};\n", typed.name, typed.default),
+        )?;
+    }
+
     Ok(())
 }
 
@@ -339,9 +554,23 @@ fn write_relation_attribute_defaults<W: Write>(db: &CanDatabase, out: &mut W) ->
 fn write_attribute_assignments<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
     for (name, value) in &db.attributes {
         let spec = db.attr_spec.get(name);
-        let value_str = format_attribute_value(value, spec);
+        // keep the stored spelling unless the bustype field has been changed since
+        let value_str = match value {
+            AttributeValue::Str(label)
+                if name == "BusType" && BusType::from_label(label) != db.bustype =>
+            {
+                format!("\"{}\"", db.bustype.to_str())
+            }
+            _ => format_attribute_value(value, spec),
+        };
         write_fmt(out, format_args!("BA_ \"{}\" {};\n", name, value_str))?;
     }
+    if bus_type_needs_definition(db) {
+        write_fmt(
+            out,
+            format_args!("BA_ \"BusType\" \"{}\";\n", db.bustype.to_str()),
+        )?;
+    }
 
     for node in db.iter_nodes() {
         for (name, value) in &node.attributes {
@@ -357,12 +586,28 @@ fn write_attribute_assignments<W: Write>(db: &CanDatabase, out: &mut W) -> io::R
     for message in db.iter_messages() {
         for (name, value) in &message.attributes {
             let spec = db.attr_spec.get(name);
-            let value_str = format_attribute_value(value, spec);
+            // typed fields win over the raw attribute value
+            let value_str = TYPED_MESSAGE_ATTRIBUTES
+                .iter()
+                .find(|typed| typed.name == name)
+                .and_then(|typed| (typed.value)(message, spec))
+                .unwrap_or_else(|| format_attribute_value(value, spec));
             write_fmt(
                 out,
                 format_args!("BA_ \"{}\" BO_ {} {};\n", name, message.id, value_str),
             )?;
         }
+        for typed in TYPED_MESSAGE_ATTRIBUTES {
+            if message.attributes.contains_key(typed.name) {
+                continue;
+            }
+            if let Some(value_str) = (typed.value)(message, db.attr_spec.get(typed.name)) {
+                write_fmt(
+                    out,
+                    format_args!("BA_ \"{}\" BO_ {} {};\n", typed.name, message.id, value_str),
+                )?;
+            }
+        }
     }
 
     for message in db.iter_messages() {
@@ -523,6 +768,20 @@ fn write_sig_valtype<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()>
     Ok(())
 }
 
+/// Outputs `VAL_TABLE_` definitions for the named global value tables.
+fn write_global_value_tables<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
+    for (name, table) in &db.value_tables {
+        write_fmt(out, format_args!("VAL_TABLE_ {}", name))?;
+        for (value, description) in table {
+            let desc = escape_dbc_string(description);
+            write_fmt(out, format_args!(" {} \"{}\"", value, desc))?;
+        }
+        write_fmt(out, format_args!(" ;\n"))?;
+    }
+
+    Ok(())
+}
+
 /// Outputs `VAL_` tables for enumerated signal values.
 fn write_value_tables<W: Write>(db: &CanDatabase, out: &mut W) -> io::Result<()> {
     for message in db.iter_messages() {
@@ -604,22 +863,6 @@ fn format_attribute_value(value: &AttributeValue, spec: Option<&AttributeSpec>)
     }
 }
 
-/// Formats floating-point values while stripping redundant trailing zeros.
-fn format_f64(value: f64) -> String {
-    if value.fract() == 0.0 {
-        format!("{:.0}", value)
-    } else {
-        let mut s = format!("{:.12}", value);
-        while s.contains('.') && s.ends_with('0') {
-            s.pop();
-        }
-        if s.ends_with('.') {
-            s.push('0');
-        }
-        s
-    }
-}
-
 /// Escapes characters so they are safe inside DBC quoted strings.
 fn escape_dbc_string(input: &str) -> String {
     let mut escaped = String::with_capacity(input.len());
@@ -653,6 +896,29 @@ fn collect_defaults_from_scope(
             .or_insert_with(|| spec.default.clone());
     }
 }
+/// Returns the typed message attributes used by some message but not defined in the database.
+fn missing_typed_message_attributes(
+    db: &CanDatabase,
+) -> impl Iterator<Item = &'static TypedMessageAttribute> + '_ {
+    TYPED_MESSAGE_ATTRIBUTES.iter().filter(|typed| {
+        !db.attr_spec.contains_key(typed.name)
+            && db.iter_messages().any(|m| (typed.value)(m, None).is_some())
+    })
+}
+
+/// Returns `true` when a CAN FD bus type is set without any `BusType` attribute to carry it.
+fn bus_type_needs_definition(db: &CanDatabase) -> bool {
+    db.bustype == BusType::CanFd
+        && !db.attr_spec.contains_key("BusType")
+        && !db.attributes.contains_key("BusType")
+}
+
+/// Formats an `ENUM` attribute signature from its entries.
+fn format_enum_definition(entries: &[&str]) -> String {
+    let joined: Vec<String> = entries.iter().map(|e| format!("\"{}\"", e)).collect();
+    format!("ENUM {}", joined.join(","))
+}
+
 /// Looks up an attribute specification regardless of its scope.
 fn lookup_attr_spec<'a>(db: &'a CanDatabase, name: &str) -> Option<&'a AttributeSpec> {
     db.attr_spec.get(name)
@@ -701,6 +967,7 @@ fn collect_independent_signals(db: &CanDatabase) -> Vec<CanSignalKey> {
 /// Synthesizes a fake message containing independent signals for export.
 fn write_independent_signals_as_fake_message<W: Write>(
     db: &CanDatabase,
+    options: &SaveOptions,
     orphans: &[CanSignalKey],
     out: &mut W,
 ) -> io::Result<()> {
@@ -712,7 +979,7 @@ fn write_independent_signals_as_fake_message<W: Write>(
         out,
         format_args!(
             "BO_ {} {}: {} {}\n",
-            AUTONET_FAKE_MSG_ID, AUTONET_FAKE_MSG_NAME, 0, AUTONET_FAKE_NODE
+            AUTONET_FAKE_MSG_ID, AUTONET_FAKE_MSG_NAME, 0, options.independent_signal_node
         ),
     )?;
 
@@ -736,14 +1003,14 @@ fn write_independent_signals_as_fake_message<W: Write>(
         let max: String = format_f64(signal.max);
         let unit: String = escape_dbc_string(&signal.unit_of_measurement);
 
-        // Receiver: use existing Node receivers, otherwise use AutoNet_XXX
+        // Receiver: use existing Node receivers, otherwise the independent signal node
         let receivers: Vec<&str> = signal
             .receiver_nodes
             .iter()
             .filter_map(|nk| db.get_node_by_key(*nk).map(|n| n.name.as_str()))
             .collect();
         let receivers_field = if receivers.is_empty() {
-            AUTONET_FAKE_NODE.to_string()
+            options.independent_signal_node.clone()
         } else {
             receivers.join(",")
         };
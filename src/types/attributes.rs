@@ -1,5 +1,16 @@
 use std::fmt;
 
+/// Swaps `min`/`max` when both are set and `min > max`.
+fn swap_if_reversed<T: PartialOrd>(min: &mut Option<T>, max: &mut Option<T>) -> bool {
+    if let (Some(lo), Some(hi)) = (min.as_ref(), max.as_ref())
+        && lo > hi
+    {
+        std::mem::swap(min, max);
+        return true;
+    }
+    false
+}
+
 /// Attribute specification pairing an optional definition and a default value.
 #[derive(Clone, Default, PartialEq)]
 pub struct AttributeSpec {
@@ -20,6 +31,18 @@ pub struct AttributeSpec {
     pub type_of_object: AttrObject,
 }
 impl AttributeSpec {
+    /// Swaps reversed numeric bounds so that `min <= max` (e.g. `INT 100 0`).
+    ///
+    /// Returns `true` if the bounds were swapped.
+    pub fn normalize_bounds(&mut self) -> bool {
+        match self.value_type {
+            AttrValueType::Int => swap_if_reversed(&mut self.int_min, &mut self.int_max),
+            AttrValueType::Hex => swap_if_reversed(&mut self.hex_min, &mut self.hex_max),
+            AttrValueType::Float => swap_if_reversed(&mut self.float_min, &mut self.float_max),
+            AttrValueType::String | AttrValueType::Enum => false,
+        }
+    }
+
     /// Human-readable lower bound, respecting the declared value type.
     pub fn minimum_to_string(&self) -> String {
         match self.value_type {
@@ -110,6 +133,63 @@ impl AttributeSpec {
     }
 }
 
+/// Value type and constraints of an attribute, as written after the name in `BA_DEF_`.
+///
+/// Used by `CanDatabase::define_attribute` to build an [`AttributeSpec`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeDef {
+    /// `INT <min> <max>`
+    Int { min: i64, max: i64 },
+    /// `HEX <min> <max>`
+    Hex { min: u64, max: u64 },
+    /// `FLOAT <min> <max>`
+    Float { min: f64, max: f64 },
+    /// `STRING`
+    String,
+    /// `ENUM "a","b",...`
+    Enum(Vec<String>),
+}
+
+impl AttributeDef {
+    /// Builds the attribute specification described by this definition.
+    pub fn to_spec(
+        &self,
+        name: &str,
+        object: AttrObject,
+        default: AttributeValue,
+    ) -> AttributeSpec {
+        let mut spec: AttributeSpec = AttributeSpec {
+            name: name.to_string(),
+            default,
+            type_of_object: object,
+            ..Default::default()
+        };
+        match self {
+            AttributeDef::Int { min, max } => {
+                spec.value_type = AttrValueType::Int;
+                spec.int_min = Some(*min);
+                spec.int_max = Some(*max);
+            }
+            AttributeDef::Hex { min, max } => {
+                spec.value_type = AttrValueType::Hex;
+                spec.hex_min = Some(*min);
+                spec.hex_max = Some(*max);
+            }
+            AttributeDef::Float { min, max } => {
+                spec.value_type = AttrValueType::Float;
+                spec.float_min = Some(*min);
+                spec.float_max = Some(*max);
+            }
+            AttributeDef::String => spec.value_type = AttrValueType::String,
+            AttributeDef::Enum(values) => {
+                spec.value_type = AttrValueType::Enum;
+                spec.enum_values = values.clone();
+            }
+        }
+        spec
+    }
+}
+
 /// Attribute value value_types as declared by `BA_DEF_` lines in DBC.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum AttrValueType {
@@ -203,3 +283,23 @@ impl fmt::Display for AttrObject {
         })
     }
 }
+
+/// Scope of an attribute definition: an entity kind (`BA_DEF_`) or a node relation (`BA_DEF_REL_`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttrScope {
+    Object(AttrObject),
+    /// `BU_SG_REL_`: node ↔ signal relation.
+    NodeSignal,
+    /// `BU_BO_REL_`: node ↔ message relation.
+    NodeMessage,
+}
+
+impl fmt::Display for AttrScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttrScope::Object(object) => write!(f, "{object}"),
+            AttrScope::NodeSignal => f.write_str("Node-Signal"),
+            AttrScope::NodeMessage => f.write_str("Node-Message"),
+        }
+    }
+}
@@ -16,15 +16,22 @@
 
 use slotmap::{Key, SlotMap, new_key_type};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 
 use crate::{
-    core::message_layout,
+    core::{dlc, message_layout},
     types::{
-        attributes::{AttrObject, AttrValueType, AttributeSpec, AttributeValue},
+        attributes::{
+            AttrObject, AttrScope, AttrValueType, AttributeDef, AttributeSpec, AttributeValue,
+        },
         errors::DatabaseError,
-        message::{CanMessage, IdFormat, MuxRole, MuxSelector},
-        node::CanNode,
-        signal::{CanSignal, Endianness, Signess},
+        frame::{
+            ByteAnnotation, ByteSignalSlice, ChecksumAlgo, DecodedSignal, FrameInterpretation,
+        },
+        message::{CanMessage, Coverage, IdFormat, MuxRole, MuxSelector},
+        node::{CanNode, NodeMessages, NodeSummary},
+        signal::{CanSignal, Endianness, SignalType, Signess},
     },
 };
 
@@ -49,6 +56,8 @@ pub struct CanDatabase {
     pub version: String,
     /// Global database comment (populated by the standalone `CM_ "..."` statement).
     pub comment: String,
+    /// Bit timing from the `BS_:` line, `None` when the section is empty.
+    pub bit_timing: Option<BitTiming>,
 
     // --- Main storage (stable-key maps) ---
     pub nodes: SlotMap<CanNodeKey, CanNode>,
@@ -60,6 +69,15 @@ pub struct CanDatabase {
     pub messages_order: Vec<CanMessageKey>,
     pub signals_order: Vec<CanSignalKey>,
 
+    // --- Global value tables (VAL_TABLE_) ---
+    /// Named value tables, reusable across signals.
+    pub value_tables: BTreeMap<String, BTreeMap<i32, String>>,
+
+    // --- Signal types (SGTYPE_) ---
+    /// Signal types by name, read for their value tables only. The `SGTYPE_`, `SGTYPE_VAL_`
+    /// and `SIG_TYPE_REF_` statements are kept verbatim in `unparsed_lines`.
+    pub signal_types: BTreeMap<String, SignalType>,
+
     // --- DB Attribute Entry ---
     pub attributes: BTreeMap<String, AttributeValue>,
 
@@ -90,6 +108,11 @@ pub struct CanDatabase {
 
     // Parsing state: last message seen (used by SG_ decoder)
     pub(crate) current_msg: Option<CanMessageKey>,
+    // Parsing state: set by a decoder that found the current line malformed
+    pub(crate) line_malformed: bool,
+
+    // Naming rule enforced by add_node / add_message / add_signal
+    pub(crate) name_policy: NamePolicy,
 
     // --- Relational Attributes (BA_REL_) ---
     // Concrete values attached to a pair of entities.
@@ -100,12 +123,107 @@ pub struct CanDatabase {
     /// BU_BO_REL_: attributes on (Node, Message) pairs.
     pub bu_bo_rel_attributes:
         HashMap<(CanNodeKey, CanMessageKey), BTreeMap<String, AttributeValue>>,
+
+    // --- Parsing diagnostics ---
+    /// Non-fatal issues found while parsing (input that was normalized or ignored).
+    pub parse_warnings: Vec<String>,
+    /// Statements with keywords this crate does not model, kept verbatim and re-emitted on save.
+    ///
+    /// This includes environment variables (`EV_`) with their `CM_ EV_` and `VAL_` lines.
+    pub unparsed_lines: Vec<String>,
 }
 
 impl CanDatabase {
+    // --------- Name Policy --------
+    /// Sets the naming rule enforced by `add_node`, `add_message` and `add_signal`.
+    ///
+    /// Existing items are not re-validated.
+    pub fn set_name_policy(&mut self, policy: NamePolicy) {
+        self.name_policy = policy;
+    }
+
+    /// Returns the naming rule currently enforced on insert.
+    pub fn name_policy(&self) -> NamePolicy {
+        self.name_policy
+    }
+
+    /// Returns `DatabaseError::InvalidName` if `name` is rejected by the current policy.
+    fn check_name(&self, name: &str) -> Result<(), DatabaseError> {
+        if self.name_policy.allows(name) {
+            Ok(())
+        } else {
+            Err(DatabaseError::InvalidName {
+                name: name.to_string(),
+                policy: self.name_policy,
+            })
+        }
+    }
+
+    /// Renames nodes, messages and signals whose names are not valid DBC identifiers.
+    ///
+    /// Each offending name is rewritten with [`NamePolicy::sanitize`]; a `_2`, `_3`, ...
+    /// suffix is appended when the result clashes with another name of the same kind.
+    /// Lookup maps are updated and the `(old, new)` pairs are returned in order of
+    /// nodes, messages, then signals.
+    pub fn sanitize_names(&mut self, policy: NamePolicy) -> Vec<(String, String)> {
+        let mut renamed: Vec<(String, String)> = Vec::new();
+
+        let mut taken: HashSet<String> = self.node_key_by_name.keys().cloned().collect();
+        for key in self.nodes_order.clone() {
+            let Some(old) = self.nodes.get(key).map(|n| n.name.clone()) else {
+                continue;
+            };
+            let Some(new) = unique_sanitized(&old, policy, &mut taken) else {
+                continue;
+            };
+            if self.node_key_by_name.get(&old.to_ascii_lowercase()) == Some(&key) {
+                self.node_key_by_name.remove(&old.to_ascii_lowercase());
+            }
+            self.node_key_by_name.insert(new.to_ascii_lowercase(), key);
+            self.nodes[key].name = new.clone();
+            renamed.push((old, new));
+        }
+
+        let mut taken: HashSet<String> = self.msg_key_by_name.keys().cloned().collect();
+        for key in self.messages_order.clone() {
+            let Some(old) = self.messages.get(key).map(|m| m.name.clone()) else {
+                continue;
+            };
+            let Some(new) = unique_sanitized(&old, policy, &mut taken) else {
+                continue;
+            };
+            if self.msg_key_by_name.get(&old.to_ascii_lowercase()) == Some(&key) {
+                self.msg_key_by_name.remove(&old.to_ascii_lowercase());
+            }
+            self.msg_key_by_name.insert(new.to_ascii_lowercase(), key);
+            self.messages[key].name = new.clone();
+            renamed.push((old, new));
+        }
+
+        let mut taken: HashSet<String> = self.sig_key_by_name.keys().cloned().collect();
+        for key in self.signals_order.clone() {
+            let Some(old) = self.signals.get(key).map(|s| s.name.clone()) else {
+                continue;
+            };
+            let Some(new) = unique_sanitized(&old, policy, &mut taken) else {
+                continue;
+            };
+            if self.sig_key_by_name.get(&old.to_ascii_lowercase()) == Some(&key) {
+                self.sig_key_by_name.remove(&old.to_ascii_lowercase());
+            }
+            self.sig_key_by_name.insert(new.to_ascii_lowercase(), key);
+            self.signals[key].name = new.clone();
+            renamed.push((old, new));
+        }
+
+        renamed
+    }
+
     // --------- Nodes --------
     /// Adds a node to the database, seeding attributes with spec defaults, and returns the `CanNodeKey`.
     pub fn add_node(&mut self, name: &str) -> Result<CanNodeKey, DatabaseError> {
+        self.check_name(name)?;
+
         // check that the node name is not already present
         if self.get_node_key_by_name(name).is_some() {
             return Err(DatabaseError::NodeAlreadyExists {
@@ -137,6 +255,27 @@ impl CanDatabase {
         Ok(key)
     }
 
+    /// Name-based [`CanDatabase::add_sender_relation`].
+    ///
+    /// A name that does not resolve yields `MessageNameNotFound`/`NodeNameNotFound`.
+    pub fn add_sender_relation_by_name(
+        &mut self,
+        message: &str,
+        node: &str,
+    ) -> Result<(), DatabaseError> {
+        let Some(msg_key) = self.get_msg_key_by_name(message) else {
+            return Err(DatabaseError::MessageNameNotFound {
+                name: message.to_string(),
+            });
+        };
+        let Some(node_key) = self.get_node_key_by_name(node) else {
+            return Err(DatabaseError::NodeNameNotFound {
+                name: node.to_string(),
+            });
+        };
+        self.add_sender_relation(msg_key, node_key)
+    }
+
     /// Link a sender node to a message, keeping both sides in sync.
     pub fn add_sender_relation(
         &mut self,
@@ -154,12 +293,18 @@ impl CanDatabase {
                 .get_node_by_key(node_key)
                 .ok_or(DatabaseError::NodeMissing { node_key })?;
 
-            // signals of the Message that needs to be added as CanNode.tx_signals
+            // signals of the Message that needs to be added as CanNode.tx_signals,
+            // skipping those with their own senders
             pending_tx = message
                 .signals
                 .iter()
                 .copied()
                 .filter(|sig| !node.tx_signals.contains(sig))
+                .filter(|&sig| {
+                    self.get_sig_by_key(sig).is_none_or(|s| {
+                        s.sender_nodes.is_empty() || s.sender_nodes.contains(&node_key)
+                    })
+                })
                 .collect();
         }
 
@@ -239,6 +384,7 @@ impl CanDatabase {
 
     /// Create a new Node from an existing one adding "_copy" to the name
     /// Messages and Signals are modified to include new node relations
+    /// Node attribute defaults missing on the source are added to the copy
     pub fn copy_node(&mut self, source_node_key: CanNodeKey) -> Result<CanNodeKey, DatabaseError> {
         let new_node: CanNode = {
             // check that the source node key correspond to a Node
@@ -257,6 +403,18 @@ impl CanDatabase {
             }
             let mut cloned: CanNode = node.clone();
             cloned.name = new_name;
+
+            // fill in spec defaults the source node lacks, keeping its own values
+            for (attr_name, spec) in self
+                .attr_spec
+                .iter()
+                .filter(|(_, s)| s.type_of_object == AttrObject::Node)
+            {
+                cloned
+                    .attributes
+                    .entry(attr_name.clone())
+                    .or_insert_with(|| spec.default.clone());
+            }
             cloned
         };
 
@@ -351,11 +509,94 @@ impl CanDatabase {
 
         for (_sig_key, signal) in self.signals.iter_mut() {
             signal.receiver_nodes.retain(|&nk| nk != node_key);
+            signal.sender_nodes.retain(|&nk| nk != node_key);
         }
 
         Ok(())
     }
 
+    /// Merges nodes whose names differ only by case into the first one in `nodes_order`
+    /// and returns how many nodes were merged away.
+    ///
+    /// Sender and receiver relations move to the kept node, attributes, comments and
+    /// relation attributes it lacks are taken over, then the duplicates are deleted.
+    pub fn deduplicate_nodes(&mut self) -> usize {
+        let mut keeper_by_name: HashMap<String, CanNodeKey> = HashMap::new();
+        let mut merges: Vec<(CanNodeKey, CanNodeKey)> = Vec::new();
+        for &node_key in &self.nodes_order {
+            let Some(node) = self.get_node_by_key(node_key) else {
+                continue;
+            };
+            match keeper_by_name.get(&node.name.to_ascii_lowercase()) {
+                Some(&keeper) => merges.push((node_key, keeper)),
+                None => {
+                    keeper_by_name.insert(node.name.to_ascii_lowercase(), node_key);
+                }
+            }
+        }
+
+        let mut merged: usize = 0;
+        for (duplicate, keeper) in merges {
+            let Some(node) = self.get_node_by_key(duplicate).cloned() else {
+                continue;
+            };
+            for &msg_key in &node.messages_sent {
+                let _ = self.add_sender_relation(msg_key, keeper);
+            }
+            for &sig_key in &node.rx_signals {
+                let _ = self.add_sig_receiver_node(sig_key, keeper);
+            }
+            if let Some(kept) = self.get_node_by_key_mut(keeper) {
+                for (name, value) in node.attributes {
+                    kept.attributes.entry(name).or_insert(value);
+                }
+                if kept.comment.is_empty() {
+                    kept.comment = node.comment;
+                }
+            }
+            let sg_rel: Vec<(CanSignalKey, BTreeMap<String, AttributeValue>)> = self
+                .bu_sg_rel_attributes
+                .iter()
+                .filter(|((nk, _), _)| *nk == duplicate)
+                .map(|((_, sk), v)| (*sk, v.clone()))
+                .collect();
+            for (sig_key, values) in sg_rel {
+                let kept = self
+                    .bu_sg_rel_attributes
+                    .entry((keeper, sig_key))
+                    .or_default();
+                for (name, value) in values {
+                    kept.entry(name).or_insert(value);
+                }
+            }
+            let bo_rel: Vec<(CanMessageKey, BTreeMap<String, AttributeValue>)> = self
+                .bu_bo_rel_attributes
+                .iter()
+                .filter(|((nk, _), _)| *nk == duplicate)
+                .map(|((_, mk), v)| (*mk, v.clone()))
+                .collect();
+            for (msg_key, values) in bo_rel {
+                let kept = self
+                    .bu_bo_rel_attributes
+                    .entry((keeper, msg_key))
+                    .or_default();
+                for (name, value) in values {
+                    kept.entry(name).or_insert(value);
+                }
+            }
+
+            if self.delete_node(duplicate).is_ok() {
+                merged += 1;
+            }
+            // the duplicate shared the keeper's case-insensitive lookup entry
+            if let Some(kept) = self.get_node_by_key(keeper) {
+                self.node_key_by_name
+                    .insert(kept.name.to_ascii_lowercase(), keeper);
+            }
+        }
+        merged
+    }
+
     /// Looks up the `CanNodeKey` for a given node name (case-insensitive).
     pub fn get_node_key_by_name(&self, name: &str) -> Option<CanNodeKey> {
         self.node_key_by_name
@@ -385,13 +626,125 @@ impl CanDatabase {
         self.get_node_by_key_mut(key)
     }
 
+    /// Returns the messages carrying at least one signal received by `node_key`, in
+    /// `messages_order`.
+    ///
+    /// Derived from `rx_signals` on every call; a missing node yields an empty list.
+    pub fn messages_received_by_node(&self, node_key: CanNodeKey) -> Vec<CanMessageKey> {
+        let Some(node) = self.get_node_by_key(node_key) else {
+            return Vec::new();
+        };
+        let received: HashSet<CanMessageKey> = node
+            .rx_signals
+            .iter()
+            .filter_map(|&k| self.get_sig_by_key(k))
+            .filter(|s| !s.message.is_null())
+            .map(|s| s.message)
+            .collect();
+
+        self.messages_order
+            .iter()
+            .copied()
+            .filter(|k| received.contains(k))
+            .collect()
+    }
+
+    /// Returns a transmit/receive overview of `node_key`.
+    ///
+    /// Received messages come from [`CanDatabase::messages_received_by_node`], so a node
+    /// receiving several signals of the same message counts it once. A missing node yields an
+    /// empty summary.
+    pub fn node_summary(&self, node_key: CanNodeKey) -> NodeSummary {
+        let Some(node) = self.get_node_by_key(node_key) else {
+            return NodeSummary::default();
+        };
+
+        NodeSummary {
+            messages_sent: node.messages_sent.clone(),
+            messages_received: self.messages_received_by_node(node_key),
+            tx_signal_count: node.tx_signals.len(),
+            rx_signal_count: node.rx_signals.len(),
+            rx_units: node
+                .rx_signals
+                .iter()
+                .filter_map(|&k| self.get_sig_by_key(k))
+                .filter(|s| !s.unit_of_measurement.is_empty())
+                .map(|s| s.unit_of_measurement.clone())
+                .collect(),
+        }
+    }
+
+    /// Returns the messages `node_key` sends and receives, in one pass over
+    /// `messages_order`.
+    ///
+    /// A message is sent when the node is among its `sender_nodes`, and received when the
+    /// node receives at least one of its signals. A missing node yields empty lists.
+    pub fn messages_touching_node(&self, node_key: CanNodeKey) -> NodeMessages {
+        let mut touching: NodeMessages = NodeMessages::default();
+        if self.get_node_by_key(node_key).is_none() {
+            return touching;
+        }
+
+        for message_key in &self.messages_order {
+            let Some(message) = self.get_message_by_key(*message_key) else {
+                continue;
+            };
+            if message.sender_nodes.contains(&node_key) {
+                touching.sent.push(*message_key);
+            }
+            if message
+                .signals(self)
+                .any(|s| s.receiver_nodes.contains(&node_key))
+            {
+                touching.received.push(*message_key);
+            }
+        }
+
+        touching
+    }
+
     // ------------- Messages ------------
     /// Adds a message, indexes its id/name and updates `current_msg` for upcoming SG_ rows.
+    ///
+    /// `byte_length` must be a legal CAN/CAN FD payload size (`0..=8`, `12`, `16`, `20`,
+    /// `24`, `32`, `48`, `64`), otherwise [`DatabaseError::InvalidDlc`] is returned.
     pub fn add_message(
         &mut self,
         name: &str,
         id: u32,
         byte_length: u16,
+    ) -> Result<CanMessageKey, DatabaseError> {
+        self.check_name(name)?;
+        if dlc::length_to_code(byte_length).is_none() {
+            return Err(DatabaseError::InvalidDlc { byte_length });
+        }
+        self.add_message_unchecked(name, id, byte_length)
+    }
+
+    /// Like [`CanDatabase::add_message`], but rounds an illegal `byte_length` up to the next
+    /// valid CAN FD payload size (e.g. `9` → `12`) instead of rejecting it.
+    ///
+    /// Lengths above 64 bytes are still rejected with [`DatabaseError::InvalidDlc`].
+    pub fn add_message_round_up(
+        &mut self,
+        name: &str,
+        id: u32,
+        byte_length: u16,
+    ) -> Result<CanMessageKey, DatabaseError> {
+        self.check_name(name)?;
+        if byte_length > 64 {
+            return Err(DatabaseError::InvalidDlc { byte_length });
+        }
+        self.add_message_unchecked(name, id, dlc::can_fd_round_up(byte_length))
+    }
+
+    /// Adds a message without validating `byte_length` or the name policy; used by importers
+    /// that must preserve whatever the source file declares.
+    pub(crate) fn add_message_unchecked(
+        &mut self,
+        name: &str,
+        id: u32,
+        byte_length: u16,
     ) -> Result<CanMessageKey, DatabaseError> {
         // check if message with provided name already exist
         if let Some(r) = self.get_msg_key_by_name(name) {
@@ -454,6 +807,67 @@ impl CanDatabase {
         Ok(msg_key)
     }
 
+    /// Creates the message `msg_name` with `id` and binds every signal that is not assigned
+    /// to a message into it, placing each one (in `signals_order`) at the first start bit
+    /// where it does not overlap the previous ones.
+    ///
+    /// The payload length is the smallest legal CAN/CAN FD size holding all signals; once
+    /// done, saving no longer needs the synthetic `AUTONET__INDEPENDENT_SIG_MSG`. Fails with
+    /// [`DatabaseError::SignalDoesNotFit`] when the signals exceed 64 bytes, and leaves the
+    /// signals untouched when the message cannot be created.
+    pub fn collect_into_message(
+        &mut self,
+        msg_name: &str,
+        id: u32,
+    ) -> Result<CanMessageKey, DatabaseError> {
+        let orphans: Vec<CanSignalKey> = self
+            .signals_order
+            .iter()
+            .copied()
+            .filter(|&k| self.get_sig_by_key(k).is_some_and(|s| s.message.is_null()))
+            .collect();
+
+        // per-byte occupancy of a 64-byte CAN FD payload
+        let mut occupied: [u8; 64] = [0; 64];
+        let mut byte_length: u16 = 0;
+        // start bits are probed on a copy and applied once the message exists
+        let mut placements: Vec<(CanSignalKey, u16)> = Vec::with_capacity(orphans.len());
+        for &sig_key in &orphans {
+            let Some(mut probe) = self.get_sig_by_key(sig_key).cloned() else {
+                continue;
+            };
+            let fits = |masks: &[(u8, u8)]| {
+                masks.iter().all(|&(byte, mask)| {
+                    occupied
+                        .get(byte as usize)
+                        .is_some_and(|used| used & mask == 0)
+                })
+            };
+            let Some((bit_start, masks)) = (0..occupied.len() as u16 * 8).find_map(|bit_start| {
+                probe.bit_start = bit_start;
+                let masks: Vec<(u8, u8)> = probe.byte_masks();
+                fits(&masks).then_some((bit_start, masks))
+            }) else {
+                return Err(DatabaseError::SignalDoesNotFit { name: probe.name });
+            };
+            for (byte, mask) in masks {
+                occupied[byte as usize] |= mask;
+                byte_length = byte_length.max(byte as u16 + 1);
+            }
+            placements.push((sig_key, bit_start));
+        }
+
+        let msg_key: CanMessageKey = self.add_message_round_up(msg_name, id, byte_length)?;
+        for (sig_key, bit_start) in placements {
+            if let Some(signal) = self.get_sig_by_key_mut(sig_key) {
+                signal.bit_start = bit_start;
+            }
+            self.add_msg_sig_relation(sig_key, msg_key, MuxRole::None, None)?;
+        }
+
+        Ok(msg_key)
+    }
+
     /// Deletes the Message identified by `msg_key`, removing every reference across the database.
     pub fn delete_message(&mut self, msg_key: CanMessageKey) -> Result<(), DatabaseError> {
         let removed_msg: CanMessage =
@@ -497,22 +911,13 @@ impl CanDatabase {
         source_msg_key: CanMessageKey,
     ) -> Result<CanMessageKey, DatabaseError> {
         // check that the source message key correspond to a Message
-        let (src_name, src_id, src_byte_len, src_comment, src_attrs, src_sender_nodes, src_signals) = {
-            let source_msg =
-                self.get_message_by_key(source_msg_key)
-                    .ok_or(DatabaseError::MessageMissing {
-                        message_key: source_msg_key,
-                    })?;
-            (
-                source_msg.name.clone(),
-                source_msg.id,
-                source_msg.byte_length,
-                source_msg.comment.clone(),
-                source_msg.attributes.clone(),
-                source_msg.sender_nodes.clone(),
-                source_msg.signals.clone(),
-            )
-        };
+        let src: CanMessage = self
+            .get_message_by_key(source_msg_key)
+            .ok_or(DatabaseError::MessageMissing {
+                message_key: source_msg_key,
+            })?
+            .clone();
+        let src_name: &str = &src.name;
 
         // check that new copy name does not already exist
         let mut copy_counter: u32 = 0;
@@ -523,24 +928,34 @@ impl CanDatabase {
         }
 
         // increment the id by 1 until it is not already existing
-        let mut new_id: u32 = src_id + 1;
+        let mut new_id: u32 = src.id + 1;
         while self.get_message_by_id(new_id).is_some() {
             new_id += 1;
         }
 
-        let new_msg_key: CanMessageKey = self.add_message(&new_name, new_id, src_byte_len)?;
+        let new_msg_key: CanMessageKey =
+            self.add_message_unchecked(&new_name, new_id, src.byte_length)?;
         let Some(new_msg) = self.get_message_by_key_mut(new_msg_key) else {
             return Err(DatabaseError::InconsistentState {
                 details: "newly created message missing",
             });
         };
 
-        // update comments and attributes
-        new_msg.comment = src_comment;
-        new_msg.attributes = src_attrs;
+        // update comments, attributes and attribute-backed fields
+        new_msg.comment = src.comment.clone();
+        new_msg.attributes = src.attributes.clone();
+        if let Some(frame_format) = src.frame_format {
+            new_msg.set_frame_format(frame_format);
+        }
+        new_msg.send_type = src.send_type.clone();
+        new_msg.delay_time = src.delay_time;
+        new_msg.cycle_time = src.cycle_time;
+        new_msg.start_delay_time = src.start_delay_time;
+        new_msg.nr_of_repetition = src.nr_of_repetition;
 
         // useful info from old_signals
-        let useful_sig_info: Vec<(CanSignalKey, MuxRole, Option<MuxSelector>)> = src_signals
+        let useful_sig_info: Vec<(CanSignalKey, MuxRole, Option<MuxSelector>)> = src
+            .signals
             .iter()
             .filter_map(|&old_sk| {
                 let s = self.get_sig_by_key(old_sk)?;
@@ -557,12 +972,20 @@ impl CanDatabase {
         // copy internal signals and attach them to new message
         for (old_sk, role, sel) in useful_sig_info {
             if let Ok(new_sk) = self.copy_signal(old_sk) {
-                let _ = self.add_msg_sig_relation(new_sk, new_msg_key, role, sel.clone());
+                let _ = self.add_msg_sig_relation_unchecked(new_sk, new_msg_key, role, sel.clone());
+                if let Some(new_msg) = self.get_message_by_key_mut(new_msg_key) {
+                    if src.checksum_signal == Some(old_sk) {
+                        new_msg.checksum_signal = Some(new_sk);
+                    }
+                    if src.counter_signal == Some(old_sk) {
+                        new_msg.counter_signal = Some(new_sk);
+                    }
+                }
             }
         }
 
         // update Nodes.message_sent
-        for node_key in src_sender_nodes {
+        for &node_key in &src.sender_nodes {
             let _ = self.add_sender_relation(new_msg_key, node_key);
         }
 
@@ -581,10 +1004,11 @@ impl CanDatabase {
         self.msg_key_by_id.get(&id).copied()
     }
 
-    /// Looks up the `CanMessageKey` by hexadecimal CAN identifier.
+    /// Looks up the `CanMessageKey` by hexadecimal CAN identifier, normalized with
+    /// [`normalize_id_hex`].
     pub fn get_msg_key_by_id_hex(&self, id_hex: &str) -> Option<CanMessageKey> {
-        // let key: String = normalize_id_hex(id_hex); // "0x...UPPERCASE"
-        self.msg_key_by_hex.get(id_hex).copied()
+        let key: String = normalize_id_hex(id_hex); // "0x...UPPERCASE"
+        self.msg_key_by_hex.get(&key).copied()
     }
 
     /// Returns an immutable reference to a message given its key.
@@ -638,6 +1062,8 @@ impl CanDatabase {
 
     // -------------- Signals ------------
     /// Adds a signal to the database and returns the corresponding `CanSignalKey`.
+    ///
+    /// Fails with `DatabaseError::InvalidName` if `name` is rejected by the name policy.
     #[allow(clippy::too_many_arguments)]
     pub fn add_signal(
         &mut self,
@@ -649,6 +1075,23 @@ impl CanDatabase {
         min: f64,
         max: f64,
         unit: &str,
+    ) -> Result<CanSignalKey, DatabaseError> {
+        self.check_name(name)?;
+        Ok(self.add_signal_unchecked(name, endian, sign, factor, offset, min, max, unit))
+    }
+
+    /// Adds a signal without checking the name policy; used by importers and copies.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn add_signal_unchecked(
+        &mut self,
+        name: &str,
+        endian: Endianness,
+        sign: Signess,
+        factor: f64,
+        offset: f64,
+        min: f64,
+        max: f64,
+        unit: &str,
     ) -> CanSignalKey {
         let mut sig: CanSignal = CanSignal {
             name: name.to_string(),
@@ -707,6 +1150,12 @@ impl CanDatabase {
         // remove the Signal from the Message.signal
         for (_msg_key, message) in self.messages.iter_mut() {
             message.signals.retain(|&sk| sk != sig_key);
+            if message.checksum_signal == Some(sig_key) {
+                message.checksum_signal = None;
+            }
+            if message.counter_signal == Some(sig_key) {
+                message.counter_signal = None;
+            }
         }
 
         Ok(())
@@ -755,6 +1204,61 @@ impl CanDatabase {
         Ok(())
     }
 
+    /// Marks `node_key` as a transmitter of `sig_key`, for signals not sent by every
+    /// sender of their message.
+    ///
+    /// Once a signal has its own senders, only those nodes list it in `tx_signals`.
+    pub fn set_signal_sender(
+        &mut self,
+        sig_key: CanSignalKey,
+        node_key: CanNodeKey,
+    ) -> Result<(), DatabaseError> {
+        if self.get_node_by_key(node_key).is_none() {
+            return Err(DatabaseError::NodeMissing { node_key });
+        }
+        let Some(signal) = self.get_sig_by_key_mut(sig_key) else {
+            return Err(DatabaseError::SignalMissing {
+                signal_key: sig_key,
+            });
+        };
+        if !signal.sender_nodes.contains(&node_key) {
+            signal.sender_nodes.push(node_key);
+        }
+        let senders: Vec<CanNodeKey> = signal.sender_nodes.clone();
+
+        for (nk, node) in self.nodes.iter_mut() {
+            if senders.contains(&nk) {
+                if !node.tx_signals.contains(&sig_key) {
+                    node.tx_signals.push(sig_key);
+                }
+            } else {
+                node.tx_signals.retain(|&k| k != sig_key);
+            }
+        }
+        Ok(())
+    }
+
+    /// Name-based [`CanDatabase::add_sig_receiver_node`].
+    ///
+    /// A name that does not resolve yields `SignalNameNotFound`/`NodeNameNotFound`.
+    pub fn add_sig_receiver_node_by_name(
+        &mut self,
+        signal: &str,
+        node: &str,
+    ) -> Result<(), DatabaseError> {
+        let Some(sig_key) = self.get_sig_key_by_name(signal) else {
+            return Err(DatabaseError::SignalNameNotFound {
+                name: signal.to_string(),
+            });
+        };
+        let Some(node_key) = self.get_node_key_by_name(node) else {
+            return Err(DatabaseError::NodeNameNotFound {
+                name: node.to_string(),
+            });
+        };
+        self.add_sig_receiver_node(sig_key, node_key)
+    }
+
     /// Remove a receiver node from an existing signal, keeping both sides in sync.
     pub fn remove_sig_receiver_node(
         &mut self,
@@ -858,12 +1362,35 @@ impl CanDatabase {
     }
 
     /// Binds a signal to a message, configuring its layout and multiplexing metadata.
+    ///
+    /// Fails with [`DatabaseError::SignalOverlap`] if the signal shares payload bits with a
+    /// signal of the message, unless both are multiplexed on disjoint selectors.
     pub fn add_msg_sig_relation(
         &mut self,
         sig_key: CanSignalKey,
         msg_key: CanMessageKey,
         mux_role: MuxRole,
         mux_selector: Option<MuxSelector>,
+    ) -> Result<CanSignalKey, DatabaseError> {
+        if let Some(existing) =
+            self.find_overlapping_signal(sig_key, msg_key, mux_role, mux_selector.as_ref())
+        {
+            return Err(DatabaseError::SignalOverlap {
+                existing,
+                incoming: sig_key,
+            });
+        }
+        self.add_msg_sig_relation_unchecked(sig_key, msg_key, mux_role, mux_selector)
+    }
+
+    /// Like [`CanDatabase::add_msg_sig_relation`], but tolerates overlapping signals; used by
+    /// importers that must preserve whatever the source file declares.
+    pub fn add_msg_sig_relation_unchecked(
+        &mut self,
+        sig_key: CanSignalKey,
+        msg_key: CanMessageKey,
+        mux_role: MuxRole,
+        mux_selector: Option<MuxSelector>,
     ) -> Result<CanSignalKey, DatabaseError> {
         // check if the CanSignal is already associated to a CanMessage
         let Some(signal) = self.get_sig_by_key(sig_key) else {
@@ -1034,34 +1561,201 @@ impl CanDatabase {
         Ok(sig_key)
     }
 
-    /// Detaches a signal from a message, reversing [`Self::add_msg_sig_relation`].
-    pub fn remove_msg_sig_relation(
-        &mut self,
+    /// Returns the first signal of `msg_key` sharing payload bits with `sig_key`, skipping
+    /// multiplexed pairs that can never be active together.
+    fn find_overlapping_signal(
+        &self,
+        sig_key: CanSignalKey,
+        msg_key: CanMessageKey,
+        mux_role: MuxRole,
+        mux_selector: Option<&MuxSelector>,
+    ) -> Option<CanSignalKey> {
+        let incoming_masks: Vec<(u8, u8)> = self.get_sig_by_key(sig_key)?.byte_masks();
+        self.find_overlap_with_masks(sig_key, msg_key, &incoming_masks, mux_role, mux_selector)
+    }
+
+    /// Same as [`Self::find_overlapping_signal`], with the payload bits of `sig_key`
+    /// given as `(byte_index, mask)` pairs instead of taken from its current layout.
+    fn find_overlap_with_masks(
+        &self,
         sig_key: CanSignalKey,
         msg_key: CanMessageKey,
+        incoming_masks: &[(u8, u8)],
+        mux_role: MuxRole,
+        mux_selector: Option<&MuxSelector>,
+    ) -> Option<CanSignalKey> {
+        let message: &CanMessage = self.get_message_by_key(msg_key)?;
+        let incoming_selector: MuxSelector = mux_selector.cloned().unwrap_or_default();
+        // same switch inference as add_msg_sig_relation_unchecked
+        let incoming_switch: Option<CanSignalKey> = match message.mux_multiplexors.as_slice() {
+            [switch] if mux_role == MuxRole::Multiplexed => Some(*switch),
+            _ => None,
+        };
+
+        message.signals.iter().copied().find(|&k| {
+            let Some(existing) = self.get_sig_by_key(k) else {
+                return false;
+            };
+            if k == sig_key {
+                return false;
+            }
+            let mux_exclusive: bool = mux_role == MuxRole::Multiplexed
+                && existing.mux_role == MuxRole::Multiplexed
+                && existing.mux_switch == incoming_switch
+                && !existing.mux_selector.intersects(&incoming_selector);
+            !mux_exclusive
+                && existing.byte_masks().iter().any(|&(byte, mask)| {
+                    incoming_masks
+                        .iter()
+                        .any(|&(b, m)| b == byte && m & mask != 0)
+                })
+        })
+    }
+
+    /// Moves a signal to a new bit layout and recompiles its extraction steps.
+    ///
+    /// When the signal belongs to a message, the layout must fit the message payload and must
+    /// not overlap its sibling signals (see [`Self::add_msg_sig_relation`]). On error the
+    /// signal is left unchanged.
+    pub fn set_signal_layout(
+        &mut self,
+        sig_key: CanSignalKey,
+        bit_start: u16,
+        bit_length: u16,
+        endian: Endianness,
     ) -> Result<(), DatabaseError> {
-        // Ensure the message exists.
-        self.get_message_by_key(msg_key)
-            .ok_or(DatabaseError::MessageMissing {
-                message_key: msg_key,
-            })?;
+        let Some(signal) = self.get_sig_by_key(sig_key) else {
+            return Err(DatabaseError::SignalMissing {
+                signal_key: sig_key,
+            });
+        };
 
-        // Snapshot the signal state and verify that it is bound to the target message.
-        let mux_snapshot: Option<(MuxRole, Option<CanSignalKey>, MuxSelector)> = {
-            let signal = self
-                .get_sig_by_key(sig_key)
-                .ok_or(DatabaseError::SignalMissing {
-                    signal_key: sig_key,
-                })?;
-            if signal.message.is_null() {
-                return Err(DatabaseError::InconsistentState {
-                    details: "Signal is not associated with any message",
+        if let Some(message) = self.get_message_by_key(signal.message) {
+            message_layout::check_signal_fits(
+                message.byte_length,
+                bit_start,
+                bit_length,
+                endian.clone(),
+            )?;
+
+            let probe: CanSignal = CanSignal {
+                bit_start,
+                bit_length,
+                endian: endian.clone(),
+                ..Default::default()
+            };
+            if let Some(existing) = self.find_overlap_with_masks(
+                sig_key,
+                signal.message,
+                &probe.byte_masks(),
+                signal.mux_role,
+                Some(&signal.mux_selector),
+            ) {
+                return Err(DatabaseError::SignalOverlap {
+                    existing,
+                    incoming: sig_key,
                 });
             }
-            if signal.message != msg_key {
-                let associated_with = if let Some(message) = self.get_message_by_key(signal.message)
-                {
-                    format!("Message '{}' (ID {})", message.name, message.id_hex)
+        }
+
+        let Some(signal) = self.get_sig_by_key_mut(sig_key) else {
+            return Err(DatabaseError::SignalMissing {
+                signal_key: sig_key,
+            });
+        };
+        signal.bit_start = bit_start;
+        signal.bit_length = bit_length;
+        signal.endian = endian;
+        signal.steps.clear();
+        signal.compile_inline();
+        Ok(())
+    }
+
+    /// Switches every signal of `msg_key` to the `target` byte order.
+    ///
+    /// Each signal keeps the bytes it spans, in mirrored order: the byte holding its MSB
+    /// under the old order holds its LSB under the new one. A payload whose bytes in that
+    /// span are reversed accordingly decodes to the same values. Nothing is changed if any
+    /// converted signal would leave the message payload.
+    pub fn convert_message_endianness(
+        &mut self,
+        msg_key: CanMessageKey,
+        target: Endianness,
+    ) -> Result<(), DatabaseError> {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+
+        let mut new_starts: Vec<(CanSignalKey, u16)> = Vec::new();
+        for &sig_key in &message.signals {
+            let Some(signal) = self.get_sig_by_key(sig_key) else {
+                continue;
+            };
+            if signal.endian == target || signal.bit_length == 0 {
+                continue;
+            }
+            let positions: Vec<u16> = message_layout::signal_bit_positions(
+                signal.bit_start,
+                signal.bit_length,
+                signal.endian.clone(),
+            );
+            let lsb: u16 = positions[0];
+            let msb: u16 = positions[positions.len() - 1];
+            // Same bytes, mirrored: the end that sat in the first byte moves to the last one
+            let bit_start: u16 = match target {
+                Endianness::Intel => (msb / 8) * 8 + lsb % 8,
+                Endianness::Motorola => (lsb / 8) * 8 + msb % 8,
+            };
+            message_layout::check_signal_fits(
+                message.byte_length,
+                bit_start,
+                signal.bit_length,
+                target.clone(),
+            )?;
+            new_starts.push((sig_key, bit_start));
+        }
+
+        for (sig_key, bit_start) in new_starts {
+            if let Some(signal) = self.get_sig_by_key_mut(sig_key) {
+                signal.bit_start = bit_start;
+                signal.endian = target.clone();
+                signal.steps.clear();
+                signal.compile_inline();
+            }
+        }
+        Ok(())
+    }
+
+    /// Detaches a signal from a message, reversing [`Self::add_msg_sig_relation`].
+    pub fn remove_msg_sig_relation(
+        &mut self,
+        sig_key: CanSignalKey,
+        msg_key: CanMessageKey,
+    ) -> Result<(), DatabaseError> {
+        // Ensure the message exists.
+        self.get_message_by_key(msg_key)
+            .ok_or(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            })?;
+
+        // Snapshot the signal state and verify that it is bound to the target message.
+        let mux_snapshot: Option<(MuxRole, Option<CanSignalKey>, MuxSelector)> = {
+            let signal = self
+                .get_sig_by_key(sig_key)
+                .ok_or(DatabaseError::SignalMissing {
+                    signal_key: sig_key,
+                })?;
+            if signal.message.is_null() {
+                return Err(DatabaseError::InconsistentState {
+                    details: "Signal is not associated with any message",
+                });
+            }
+            if signal.message != msg_key {
+                let associated_with = if let Some(message) = self.get_message_by_key(signal.message)
+                {
+                    format!("Message '{}' (ID {})", message.name, message.id_hex)
                 } else {
                     "An unknown message".to_string()
                 };
@@ -1094,6 +1788,12 @@ impl CanDatabase {
                     details: "Signal not registered within the message.",
                 });
             }
+            if message.checksum_signal == Some(sig_key) {
+                message.checksum_signal = None;
+            }
+            if message.counter_signal == Some(sig_key) {
+                message.counter_signal = None;
+            }
 
             if let Some((role, switch, _selector)) = &mux_snapshot {
                 match role {
@@ -1231,7 +1931,7 @@ impl CanDatabase {
             copy_counter += 1;
         }
 
-        let new_sig_key: CanSignalKey = self.add_signal(
+        let new_sig_key: CanSignalKey = self.add_signal_unchecked(
             &new_name, src_endian, src_sign, src_factor, src_offset, src_min, src_max, &src_unit,
         );
         {
@@ -1247,6 +1947,8 @@ impl CanDatabase {
             new_sig.value_table = src_value_table;
             new_sig.bit_length = bit_length;
             new_sig.bit_start = bit_start;
+            new_sig.steps.clear();
+            new_sig.compile_inline();
 
             for node_key in src_receivers {
                 let _ = self.add_sig_receiver_node(new_sig_key, node_key);
@@ -1256,6 +1958,43 @@ impl CanDatabase {
         Ok(new_sig_key)
     }
 
+    /// Copies `src_sig_key` (new name with `_copy` suffix) and attaches the copy to `dest_msg_key`
+    /// at the same bit position, keeping its multiplexing role.
+    ///
+    /// The layout is validated against the destination DLC before anything is created;
+    /// on failure no copy is left behind.
+    pub fn copy_signal_to_message(
+        &mut self,
+        src_sig_key: CanSignalKey,
+        dest_msg_key: CanMessageKey,
+    ) -> Result<CanSignalKey, DatabaseError> {
+        let Some(src) = self.get_sig_by_key(src_sig_key) else {
+            return Err(DatabaseError::SignalMissing {
+                signal_key: src_sig_key,
+            });
+        };
+        let (bit_start, bit_length, endian) = (src.bit_start, src.bit_length, src.endian.clone());
+        let mux_role: MuxRole = src.mux_role;
+        let mux_selector: Option<MuxSelector> =
+            (mux_role == MuxRole::Multiplexed).then(|| src.mux_selector.clone());
+
+        let Some(dest) = self.get_message_by_key(dest_msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: dest_msg_key,
+            });
+        };
+        message_layout::check_signal_fits(dest.byte_length, bit_start, bit_length, endian)?;
+
+        let new_sig_key: CanSignalKey = self.copy_signal(src_sig_key)?;
+        if let Err(err) =
+            self.add_msg_sig_relation(new_sig_key, dest_msg_key, mux_role, mux_selector)
+        {
+            let _ = self.delete_signal(new_sig_key);
+            return Err(err);
+        }
+        Ok(new_sig_key)
+    }
+
     /// Returns `true` if the signal exists and is bound to a message.
     pub fn signal_has_message(&self, signal_key: CanSignalKey) -> bool {
         self.get_sig_by_key(signal_key)
@@ -1309,6 +2048,1142 @@ impl CanDatabase {
             .iter()
             .filter_map(|&k| self.signals.get(k))
     }
+    /// Iterate the classic CAN messages (see [`CanMessage::is_fd`]) following `messages_order`.
+    pub fn iter_can_messages(&self) -> impl Iterator<Item = &CanMessage> + '_ {
+        self.iter_messages().filter(|m| !m.is_fd())
+    }
+    /// Iterate the CAN FD messages (see [`CanMessage::is_fd`]) following `messages_order`.
+    pub fn iter_canfd_messages(&self) -> impl Iterator<Item = &CanMessage> + '_ {
+        self.iter_messages().filter(|m| m.is_fd())
+    }
+    /// Iterate the signals of `msg_key` following `message.signals` order, skipping dangling keys.
+    /// Yields nothing if the message does not exist.
+    pub fn iter_message_signals(
+        &self,
+        msg_key: CanMessageKey,
+    ) -> impl Iterator<Item = &CanSignal> + '_ {
+        self.messages
+            .get(msg_key)
+            .map(|m| m.signals.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|&k| self.signals.get(k))
+    }
+    /// Iterate the signals of `msg_key` sorted by `bit_start` (layout order, useful for display).
+    pub fn iter_message_signals_by_bit(
+        &self,
+        msg_key: CanMessageKey,
+    ) -> impl Iterator<Item = &CanSignal> + '_ {
+        let mut signals: Vec<&CanSignal> = self.iter_message_signals(msg_key).collect();
+        signals.sort_by_key(|s| s.bit_start);
+        signals.into_iter()
+    }
+    /// Iterate the `BU_SG_REL_` attribute values with resolved node and signal, skipping
+    /// pairs whose keys are dangling. Pair order is unspecified.
+    pub fn iter_bu_sg_rel(
+        &self,
+    ) -> impl Iterator<Item = (&CanNode, &CanSignal, &BTreeMap<String, AttributeValue>)> + '_ {
+        self.bu_sg_rel_attributes
+            .iter()
+            .filter_map(|(&(node_key, sig_key), attrs)| {
+                Some((self.nodes.get(node_key)?, self.signals.get(sig_key)?, attrs))
+            })
+    }
+    /// Iterate the `BU_BO_REL_` attribute values with resolved node and message, skipping
+    /// pairs whose keys are dangling. Pair order is unspecified.
+    pub fn iter_bu_bo_rel(
+        &self,
+    ) -> impl Iterator<Item = (&CanNode, &CanMessage, &BTreeMap<String, AttributeValue>)> + '_ {
+        self.bu_bo_rel_attributes
+            .iter()
+            .filter_map(|(&(node_key, msg_key), attrs)| {
+                Some((
+                    self.nodes.get(node_key)?,
+                    self.messages.get(msg_key)?,
+                    attrs,
+                ))
+            })
+    }
+    /// Number of nodes in `nodes_order` (the set `iter_nodes` walks).
+    pub fn node_count(&self) -> usize {
+        self.nodes_order.len()
+    }
+    /// Number of messages in `messages_order` (the set `iter_messages` walks).
+    pub fn message_count(&self) -> usize {
+        self.messages_order.len()
+    }
+    /// Number of signals in `signals_order` (the set `iter_signals` walks).
+    pub fn signal_count(&self) -> usize {
+        self.signals_order.len()
+    }
+    /// Number of signals in `signals_order` bound to a message.
+    pub fn bound_signal_count(&self) -> usize {
+        self.iter_signals().filter(|s| !s.message.is_null()).count()
+    }
+
+    // -------------- Queries ---------------
+    /// Returns the signals whose unit matches `unit` (trimmed, case-insensitive), in `signals_order`.
+    pub fn find_signals_by_unit(&self, unit: &str) -> Vec<CanSignalKey> {
+        let unit: &str = unit.trim();
+        self.signals_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_sig_by_key(k)
+                    .is_some_and(|s| s.unit_of_measurement.trim().eq_ignore_ascii_case(unit))
+            })
+            .collect()
+    }
+
+    /// Returns the messages whose name contains `pattern` (case-insensitive), in `messages_order`.
+    pub fn find_messages_matching(&self, pattern: &str) -> Vec<CanMessageKey> {
+        let pattern: String = pattern.to_ascii_lowercase();
+        self.messages_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_message_by_key(k)
+                    .is_some_and(|m| m.name.to_ascii_lowercase().contains(&pattern))
+            })
+            .collect()
+    }
+
+    /// Returns the signals whose name contains `pattern` (case-insensitive), in `signals_order`.
+    pub fn find_signals_matching(&self, pattern: &str) -> Vec<CanSignalKey> {
+        let pattern: String = pattern.to_ascii_lowercase();
+        self.signals_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_sig_by_key(k)
+                    .is_some_and(|s| s.name.to_ascii_lowercase().contains(&pattern))
+            })
+            .collect()
+    }
+
+    /// Returns the messages whose name matches `re`, in `messages_order`.
+    #[cfg(feature = "regex")]
+    pub fn find_messages_regex(&self, re: &regex::Regex) -> Vec<CanMessageKey> {
+        self.messages_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_message_by_key(k)
+                    .is_some_and(|m| re.is_match(&m.name))
+            })
+            .collect()
+    }
+
+    /// Returns the signals whose name matches `re`, in `signals_order`.
+    #[cfg(feature = "regex")]
+    pub fn find_signals_regex(&self, re: &regex::Regex) -> Vec<CanSignalKey> {
+        self.signals_order
+            .iter()
+            .copied()
+            .filter(|&k| self.get_sig_by_key(k).is_some_and(|s| re.is_match(&s.name)))
+            .collect()
+    }
+
+    /// Returns the signals whose [`CanSignal::effective_range`] overlaps `[min, max]` (bounds
+    /// inclusive), in `signals_order`.
+    pub fn find_signals_in_range(&self, min: f64, max: f64) -> Vec<CanSignalKey> {
+        self.signals_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_sig_by_key(k).is_some_and(|s| {
+                    let (lo, hi) = s.effective_range();
+                    lo <= max && hi >= min
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the signals of `msg_key` with at least one bit in the payload bytes
+    /// `start_byte..=end_byte`, in message order.
+    pub fn signals_in_byte_range(
+        &self,
+        msg_key: CanMessageKey,
+        start_byte: u16,
+        end_byte: u16,
+    ) -> Vec<CanSignalKey> {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Vec::new();
+        };
+        message
+            .signals
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_sig_by_key(k).is_some_and(|s| {
+                    s.byte_masks()
+                        .iter()
+                        .any(|&(byte, _)| (start_byte..=end_byte).contains(&(byte as u16)))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the bits of `msg_key` used by at least one of its signals, as in
+    /// [`CanSignal::occupied_mask`] (0 for a missing message).
+    pub fn message_used_mask(&self, msg_key: CanMessageKey) -> u64 {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return 0;
+        };
+        message
+            .signals(self)
+            .fold(0, |acc, s| acc | s.occupied_mask(message.byte_length))
+    }
+
+    /// Reports how much of the payload of `msg_key` is used by its signals and lists
+    /// the free bit ranges. A missing message yields an empty coverage.
+    pub fn message_coverage(&self, msg_key: CanMessageKey) -> Coverage {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Coverage::default();
+        };
+
+        let mut occupancy: Vec<u8> = vec![0; message.byte_length as usize];
+        for signal in message.signals(self) {
+            for (byte, mask) in signal.byte_masks() {
+                if let Some(slot) = occupancy.get_mut(byte as usize) {
+                    *slot |= mask;
+                }
+            }
+        }
+
+        let total_bits: u16 = message.byte_length * 8;
+        let used_bits: u16 = occupancy.iter().map(|b| b.count_ones() as u16).sum();
+        let mut gaps: Vec<(u16, u16)> = Vec::new();
+        let mut gap_start: Option<u16> = None;
+        for bit in 0..total_bits {
+            let used: bool = occupancy[(bit / 8) as usize] & (1 << (bit % 8)) != 0;
+            match (used, gap_start) {
+                (false, None) => gap_start = Some(bit),
+                (true, Some(start)) => {
+                    gaps.push((start, bit - 1));
+                    gap_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = gap_start {
+            gaps.push((start, total_bits - 1));
+        }
+
+        Coverage {
+            used_bits,
+            free_bits: total_bits - used_bits,
+            total_bits,
+            gaps,
+        }
+    }
+
+    /// Returns the payload size of all messages together, in bits.
+    pub fn total_payload_bits(&self) -> u64 {
+        self.iter_messages().map(|m| m.byte_length as u64 * 8).sum()
+    }
+
+    /// Counts the signals by byte order, returned as `(intel, motorola)`.
+    pub fn endianness_histogram(&self) -> (usize, usize) {
+        self.iter_signals()
+            .fold((0, 0), |(intel, motorola), s| match s.endian {
+                Endianness::Intel => (intel + 1, motorola),
+                Endianness::Motorola => (intel, motorola + 1),
+            })
+    }
+
+    /// Returns the messages containing both Intel and Motorola signals, in `messages_order`.
+    pub fn find_mixed_endianness_messages(&self) -> Vec<CanMessageKey> {
+        self.messages_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_message_by_key(k).is_some_and(|m| {
+                    let mut signals = m.signals(self);
+                    let Some(first) = signals.next() else {
+                        return false;
+                    };
+                    signals.any(|s| s.endian != first.endian)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the transmissions of the cyclic messages (non-zero `cycle_time`) in
+    /// `[0, duration_ms]`, all starting at 0 ms, as `(time_ms, msg_key)` ordered by time then ID.
+    ///
+    /// Both ends are included: a 10 ms message over 40 ms is sent at 0, 10, 20, 30 and 40 ms.
+    pub fn simulate_schedule(&self, duration_ms: u32) -> Vec<(u32, CanMessageKey)> {
+        let mut events: Vec<(u32, u32, CanMessageKey)> = Vec::new();
+        for &msg_key in &self.messages_order {
+            let Some(message) = self.get_message_by_key(msg_key) else {
+                continue;
+            };
+            let Some(cycle) = message.cycle_time.filter(|&c| c > 0) else {
+                continue;
+            };
+            events.extend(
+                (0..=duration_ms)
+                    .step_by(cycle as usize)
+                    .map(|time| (time, message.id, msg_key)),
+            );
+        }
+        events.sort_by_key(|&(time, id, _)| (time, id));
+        events
+            .into_iter()
+            .map(|(time, _, msg_key)| (time, msg_key))
+            .collect()
+    }
+
+    /// Returns the messages whose payload length contradicts their declared frame type:
+    /// more than 8 bytes while `VFrameFormat` (or, without it, `msgtype`) says classic CAN.
+    pub fn ambiguous_frame_formats(&self) -> Vec<CanMessageKey> {
+        self.messages_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_message_by_key(k)
+                    .is_some_and(|m| m.byte_length > 8 && !m.is_fd())
+            })
+            .collect()
+    }
+
+    /// Returns the messages whose send type is `Cyclic` (case-insensitive), in `messages_order`.
+    pub fn cyclic_messages(&self) -> Vec<CanMessageKey> {
+        self.messages_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_message_by_key(k).is_some_and(|m| {
+                    m.send_type
+                        .as_deref()
+                        .is_some_and(|st| st.eq_ignore_ascii_case("Cyclic"))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the name of the global value table identical to the signal's own table, if any.
+    pub fn matching_value_table(&self, sig_key: CanSignalKey) -> Option<&str> {
+        let signal: &CanSignal = self.get_sig_by_key(sig_key)?;
+        if signal.value_table.is_empty() {
+            return None;
+        }
+        self.value_tables
+            .iter()
+            .find(|(_, table)| **table == signal.value_table)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the value table of `sig_key`: its own `VAL_` table or, when that is empty, the
+    /// table of its signal type (`SGTYPE_VAL_`, else the `VAL_TABLE_` the type names).
+    ///
+    /// Empty if the signal does not exist or no table applies.
+    pub fn effective_value_table(&self, sig_key: CanSignalKey) -> BTreeMap<i32, String> {
+        let Some(signal) = self.get_sig_by_key(sig_key) else {
+            return BTreeMap::new();
+        };
+        if !signal.value_table.is_empty() {
+            return signal.value_table.clone();
+        }
+        let Some(signal_type) = signal
+            .signal_type
+            .as_ref()
+            .and_then(|name| self.signal_types.get(name))
+        else {
+            return BTreeMap::new();
+        };
+        if !signal_type.value_table.is_empty() {
+            return signal_type.value_table.clone();
+        }
+        signal_type
+            .value_table_name
+            .as_ref()
+            .and_then(|name| self.value_tables.get(name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Maps every message of `messages_order` to its signal keys, in `message.signals`
+    /// order, skipping dangling keys. Build it once for reports needing every message.
+    pub fn signals_grouped_by_message(&self) -> HashMap<CanMessageKey, Vec<CanSignalKey>> {
+        self.messages_order
+            .iter()
+            .filter_map(|&k| self.get_message_by_key(k).map(|m| (k, m)))
+            .map(|(k, message)| {
+                let signals: Vec<CanSignalKey> = message
+                    .signals
+                    .iter()
+                    .copied()
+                    .filter(|&s| self.signals.contains_key(s))
+                    .collect();
+                (k, signals)
+            })
+            .collect()
+    }
+
+    /// Returns `(signal, raw_min, raw_max, phys_min, phys_max)` for every signal, in
+    /// `signals_order`.
+    ///
+    /// The raw bounds come from [`CanSignal::raw_range`] (saturated to `i64`), the physical
+    /// ones apply factor and offset to them, ordered even for a negative factor.
+    pub fn signal_ranges(&self) -> Vec<(CanSignalKey, i64, i64, f64, f64)> {
+        self.signals_order
+            .iter()
+            .filter_map(|&k| self.get_sig_by_key(k).map(|s| (k, s)))
+            .map(|(k, signal)| {
+                let (raw_min, raw_max) = signal.raw_range();
+                let a: f64 = raw_min * signal.factor + signal.offset;
+                let b: f64 = raw_max * signal.factor + signal.offset;
+                (k, raw_min as i64, raw_max as i64, a.min(b), a.max(b))
+            })
+            .collect()
+    }
+
+    /// Lists the database items a cleanup could drop or fix.
+    ///
+    /// Reports nodes that neither send nor receive anything, signals not bound to a
+    /// message, and value-table entries whose raw value the signal cannot represent.
+    pub fn unused(&self) -> UnusedReport {
+        let nodes: Vec<CanNodeKey> = self
+            .nodes_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_node_by_key(k).is_some_and(|n| {
+                    n.messages_sent.is_empty() && n.tx_signals.is_empty() && n.rx_signals.is_empty()
+                })
+            })
+            .collect();
+
+        let orphan_signals: Vec<CanSignalKey> = self
+            .signals_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_sig_by_key(k)
+                    .is_some_and(|s| self.get_message_by_key(s.message).is_none())
+            })
+            .collect();
+
+        let mut value_table_out_of_range: Vec<(CanSignalKey, i32)> = Vec::new();
+        for &sig_key in &self.signals_order {
+            let Some(signal) = self.get_sig_by_key(sig_key) else {
+                continue;
+            };
+            let (min, max) = signal.raw_range();
+            for &raw in signal.value_table.keys() {
+                if (raw as f64) < min || (raw as f64) > max {
+                    value_table_out_of_range.push((sig_key, raw));
+                }
+            }
+        }
+
+        UnusedReport {
+            nodes,
+            orphan_signals,
+            value_table_out_of_range,
+        }
+    }
+
+    /// Deletes the unused nodes and orphan signals reported by [`CanDatabase::unused`].
+    ///
+    /// Out-of-range value-table entries are left untouched.
+    pub fn prune_unused(&mut self) {
+        let report: UnusedReport = self.unused();
+        for node_key in report.nodes {
+            let _ = self.delete_node(node_key);
+        }
+        for sig_key in report.orphan_signals {
+            let _ = self.delete_signal(sig_key);
+        }
+    }
+
+    /// Renders the database as an indented outline following the order views: each node
+    /// with the messages it sends (`tx`) and receives (`rx`), then each message with id and
+    /// payload length and its signals with `bit_start|bit_length`, byte order and sign.
+    ///
+    /// ```text
+    /// Nodes:
+    ///   ECU1
+    ///     tx: Engine (0x100)
+    /// Messages:
+    ///   Engine 0x100 [8 bytes]
+    ///     Speed 0|16 Intel Unsigned
+    /// ```
+    pub fn to_tree_string(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut out: String = String::new();
+        let message_label = |k: &CanMessageKey| {
+            self.get_message_by_key(*k)
+                .map(|m| format!("{} ({})", m.name, m.id_hex))
+        };
+
+        let _ = writeln!(out, "Nodes:");
+        for &node_key in &self.nodes_order {
+            let Some(node) = self.get_node_by_key(node_key) else {
+                continue;
+            };
+            let _ = writeln!(out, "  {}", node.name);
+            let touching: NodeMessages = self.messages_touching_node(node_key);
+            for label in touching.sent.iter().filter_map(message_label) {
+                let _ = writeln!(out, "    tx: {label}");
+            }
+            for label in touching.received.iter().filter_map(message_label) {
+                let _ = writeln!(out, "    rx: {label}");
+            }
+        }
+
+        let _ = writeln!(out, "Messages:");
+        for message in self.iter_messages() {
+            let _ = writeln!(
+                out,
+                "  {} {} [{} bytes]",
+                message.name, message.id_hex, message.byte_length
+            );
+            for signal in message.signals(self) {
+                let _ = writeln!(
+                    out,
+                    "    {} {}|{} {} {}",
+                    signal.name, signal.bit_start, signal.bit_length, signal.endian, signal.sign
+                );
+            }
+        }
+
+        out
+    }
+
+    // -------------- Decoding ---------------
+    /// Decodes `sig_key` from `payload` and returns its physical value together with the
+    /// value-table description of the raw value (if the raw value is enumerated).
+    pub fn decode_signal_labeled(
+        &self,
+        msg_key: CanMessageKey,
+        sig_key: CanSignalKey,
+        payload: &[u8],
+    ) -> Result<(f64, Option<String>), DatabaseError> {
+        let signal: &CanSignal = self.get_sig_in_message(msg_key, sig_key)?;
+        let raw: i64 = signal.extract_raw_i64(payload);
+        let value: f64 = signal.extract_physical(payload);
+        let label: Option<String> = signal.label_for_raw(raw).map(str::to_string);
+        Ok((value, label))
+    }
+
+    /// Decodes an incoming frame: looks the message up by CAN `id`, decodes every signal active
+    /// for `payload` (multiplexing honored) and attaches value-table labels and units.
+    ///
+    /// Extended IDs are also matched against the DBC form with bit 31 set.
+    /// Returns `None` if no message has this ID.
+    pub fn interpret_frame(&self, id: u32, payload: &[u8]) -> Option<FrameInterpretation> {
+        let msg_key: CanMessageKey = self
+            .get_msg_key_by_id(id)
+            .or_else(|| self.get_msg_key_by_id(id | DBC_EXTENDED_ID_FLAG))?;
+        let message: &CanMessage = self.get_message_by_key(msg_key)?;
+
+        let mut interpretation: FrameInterpretation = FrameInterpretation {
+            message: msg_key,
+            message_name: message.name.clone(),
+            ..Default::default()
+        };
+
+        for &sig_key in &message.signals {
+            let Some(signal) = self.get_sig_by_key(sig_key) else {
+                continue;
+            };
+            if !self.signal_active_in_payload(signal, payload) {
+                continue;
+            }
+            let raw: i64 = signal.extract_raw_i64(payload);
+            interpretation.signals.push(DecodedSignal {
+                signal: sig_key,
+                name: signal.name.clone(),
+                raw,
+                value: signal.extract_physical(payload),
+                label: signal.label_for_raw(raw).map(str::to_string),
+                unit: signal.unit_of_measurement.clone(),
+            });
+        }
+
+        // a switch value that selects none of its multiplexed signals is unknown
+        interpretation.unknown_mux = message.mux_multiplexors.iter().any(|&switch_key| {
+            let Some(switch) = self.get_sig_by_key(switch_key) else {
+                return false;
+            };
+            let value: u64 = switch.extract_raw_u64(payload);
+            let mut cases = message
+                .signals(self)
+                .filter(|s| s.mux_role == MuxRole::Multiplexed && s.mux_switch == Some(switch_key))
+                .peekable();
+            cases.peek().is_some() && !cases.any(|s| s.mux_selector.matches(value))
+        });
+
+        Some(interpretation)
+    }
+
+    /// Formats a decoded frame for display: a header with message name, ID and payload bytes,
+    /// then one line per active signal as `name = value unit (raw=0x..)`, where the value is
+    /// replaced by its value-table description when one matches.
+    ///
+    /// Returns an empty string if the message does not exist.
+    pub fn format_frame(&self, msg_key: CanMessageKey, data: &[u8]) -> String {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return String::new();
+        };
+
+        let bytes: Vec<String> = data.iter().map(|b| format!("{b:02X}")).collect();
+        let mut out: String = format!(
+            "{} ({}) [{}]\n",
+            message.name,
+            message.id_hex,
+            bytes.join(" ")
+        );
+
+        for signal in message.signals(self) {
+            if !self.signal_active_in_payload(signal, data) {
+                continue;
+            }
+            let raw_bits: u64 = signal.extract_raw_u64(data);
+            let value: String = match signal.label_for_raw(signal.extract_raw_i64(data)) {
+                Some(label) => label.to_string(),
+                None if signal.unit_of_measurement.is_empty() => {
+                    signal.extract_physical(data).to_string()
+                }
+                None => format!(
+                    "{} {}",
+                    signal.extract_physical(data),
+                    signal.unit_of_measurement
+                ),
+            };
+            out.push_str(&format!(
+                "  {} = {} (raw=0x{:X})\n",
+                signal.name, value, raw_bits
+            ));
+        }
+
+        out
+    }
+
+    /// Annotates each byte of `payload` with the signals of `msg_key` that overlap it and
+    /// their decoded value. Multiplexed signals are included only when selected by the payload.
+    ///
+    /// Returns an empty vector if the message does not exist.
+    pub fn message_annotation(
+        &self,
+        msg_key: CanMessageKey,
+        payload: &[u8],
+    ) -> Vec<ByteAnnotation> {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Vec::new();
+        };
+
+        let mut annotations: Vec<ByteAnnotation> = payload
+            .iter()
+            .enumerate()
+            .map(|(byte_index, &value)| ByteAnnotation {
+                byte_index,
+                value,
+                signals: Vec::new(),
+            })
+            .collect();
+
+        for &sig_key in &message.signals {
+            let Some(signal) = self.get_sig_by_key(sig_key) else {
+                continue;
+            };
+            if !self.signal_active_in_payload(signal, payload) {
+                continue;
+            }
+            let physical: f64 = signal.extract_physical(payload);
+            for st in &signal.steps {
+                let Some(annotation) = annotations.get_mut(st.byte_index as usize) else {
+                    continue;
+                };
+                let mask: u8 = (((1u16 << st.width) - 1) << st.src_lsb) as u8;
+                annotation.signals.push(ByteSignalSlice {
+                    signal: sig_key,
+                    name: signal.name.clone(),
+                    mask,
+                    bits: annotation.value & mask,
+                    physical,
+                });
+            }
+        }
+
+        annotations
+    }
+
+    /// Returns `false` for multiplexed signals whose selector does not match the switch value
+    /// in `payload`; every other signal is active.
+    fn signal_active_in_payload(&self, signal: &CanSignal, payload: &[u8]) -> bool {
+        if signal.mux_role != MuxRole::Multiplexed {
+            return true;
+        }
+        match signal.mux_switch.and_then(|k| self.get_sig_by_key(k)) {
+            Some(switch) => signal.mux_selector.matches(switch.extract_raw_u64(payload)),
+            None => true,
+        }
+    }
+
+    /// Returns the signal addressed by `sig_key`, checking that it belongs to `msg_key`.
+    fn get_sig_in_message(
+        &self,
+        msg_key: CanMessageKey,
+        sig_key: CanSignalKey,
+    ) -> Result<&CanSignal, DatabaseError> {
+        self.get_message_by_key(msg_key)
+            .ok_or(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            })?;
+        let signal: &CanSignal =
+            self.get_sig_by_key(sig_key)
+                .ok_or(DatabaseError::SignalMissing {
+                    signal_key: sig_key,
+                })?;
+        if signal.message.is_null() {
+            return Err(DatabaseError::InconsistentState {
+                details: "Signal is not associated with any message",
+            });
+        }
+        if signal.message != msg_key {
+            let associated_with = if let Some(message) = self.get_message_by_key(signal.message) {
+                format!("Message '{}' (ID {})", message.name, message.id_hex)
+            } else {
+                "An unknown message".to_string()
+            };
+            return Err(DatabaseError::SignalAlreadyAssociated {
+                signal: signal.name.clone(),
+                associated_with,
+            });
+        }
+        Ok(signal)
+    }
+
+    /// Builds the payload of `msg_key` from physical values keyed by signal name.
+    ///
+    /// The buffer starts zeroed at `byte_length`; non-multiplexed signals missing from
+    /// `values` take their `GenSigStartValue` (a raw value) when set. A name that is not a
+    /// signal of the message yields [`DatabaseError::SignalNameNotFound`].
+    pub fn build_frame(
+        &self,
+        msg_key: CanMessageKey,
+        values: &HashMap<String, f64>,
+    ) -> Result<Vec<u8>, DatabaseError> {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+        let mut buf: Vec<u8> = vec![0; message.byte_length as usize];
+
+        for name in values.keys() {
+            if !message.signals(self).any(|s| &s.name == name) {
+                return Err(DatabaseError::SignalNameNotFound { name: name.clone() });
+            }
+        }
+
+        for signal in message.signals(self) {
+            if values.contains_key(&signal.name) || signal.mux_role == MuxRole::Multiplexed {
+                continue;
+            }
+            let start: Option<f64> = match signal.attributes.get("GenSigStartValue") {
+                Some(AttributeValue::Int(v)) => Some(*v as f64),
+                Some(AttributeValue::Float(v)) => Some(*v),
+                _ => None,
+            };
+            if let Some(raw) = start.filter(|&raw| raw != 0.0) {
+                signal.encode_physical(&mut buf, raw * signal.factor + signal.offset);
+            }
+        }
+
+        for signal in message.signals(self) {
+            if let Some(&phys) = values.get(&signal.name) {
+                signal.encode_physical(&mut buf, phys);
+            }
+        }
+
+        Ok(buf)
+    }
+
+    /// Writes into `buf` the raw value of `signal` (a signal of `msg_key`, by name) whose
+    /// value-table description is `label`, matched case-insensitively.
+    ///
+    /// Returns [`DatabaseError::SignalNameNotFound`] when the message has no such signal and
+    /// [`DatabaseError::ValueTableEntryMissing`] when no entry carries `label`.
+    pub fn encode_labeled(
+        &self,
+        msg_key: CanMessageKey,
+        signal: &str,
+        label: &str,
+        buf: &mut [u8],
+    ) -> Result<(), DatabaseError> {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+        let Some(sig) = message.signals(self).find(|s| s.name == signal) else {
+            return Err(DatabaseError::SignalNameNotFound {
+                name: signal.to_string(),
+            });
+        };
+        let Some(raw) = sig.raw_for_label(label) else {
+            return Err(DatabaseError::ValueTableEntryMissing {
+                signal: sig.name.clone(),
+                entry: label.to_string(),
+            });
+        };
+        sig.encode_raw_u64(buf, raw as u64 & raw_mask(sig));
+        Ok(())
+    }
+
+    // -------------- Checksum & Counter ---------------
+    /// Marks `sig_key` as the checksum signal of `msg_key`.
+    pub fn set_checksum_signal(
+        &mut self,
+        msg_key: CanMessageKey,
+        sig_key: CanSignalKey,
+    ) -> Result<(), DatabaseError> {
+        self.get_sig_in_message(msg_key, sig_key)?;
+        if let Some(message) = self.get_message_by_key_mut(msg_key) {
+            message.checksum_signal = Some(sig_key);
+        }
+        Ok(())
+    }
+
+    /// Marks `sig_key` as the rolling counter signal of `msg_key`.
+    pub fn set_counter_signal(
+        &mut self,
+        msg_key: CanMessageKey,
+        sig_key: CanSignalKey,
+    ) -> Result<(), DatabaseError> {
+        self.get_sig_in_message(msg_key, sig_key)?;
+        if let Some(message) = self.get_message_by_key_mut(msg_key) {
+            message.counter_signal = Some(sig_key);
+        }
+        Ok(())
+    }
+
+    /// Prepares `buf` for transmission: increments the counter signal (wrapping at its bit
+    /// width), then writes the checksum signal computed by `algo`.
+    ///
+    /// The checksum covers every payload byte not touched by the checksum signal itself and is
+    /// truncated to the signal width. Unset counter/checksum signals are skipped.
+    pub fn finalize_frame(
+        &self,
+        msg_key: CanMessageKey,
+        buf: &mut [u8],
+        algo: ChecksumAlgo,
+    ) -> Result<(), DatabaseError> {
+        let Some(message) = self.get_message_by_key(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+
+        if let Some(sig_key) = message.counter_signal {
+            let counter: &CanSignal = self.get_sig_in_message(msg_key, sig_key)?;
+            let next: u64 = counter.extract_raw_u64(buf).wrapping_add(1) & raw_mask(counter);
+            counter.encode_raw_u64(buf, next);
+        }
+
+        if let Some(sig_key) = message.checksum_signal {
+            let checksum: &CanSignal = self.get_sig_in_message(msg_key, sig_key)?;
+            let covered: Vec<u8> = buf
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !checksum.steps.iter().any(|st| st.byte_index as usize == *i))
+                .map(|(_, &b)| b)
+                .collect();
+            let value: u64 = algo.compute(&covered) as u64 & raw_mask(checksum);
+            checksum.encode_raw_u64(buf, value);
+        }
+
+        Ok(())
+    }
+
+    // -------------- Subsets ---------------
+    /// Builds a new database holding only the messages in `msg_keys`, the signals they own
+    /// and the nodes sending or receiving them (e.g. to generate a per-ECU DBC).
+    ///
+    /// General information, global value tables and attribute definitions are carried over.
+    /// Keys are regenerated: keys of `self` are not valid on the returned database.
+    pub fn subset(&self, msg_keys: &[CanMessageKey]) -> CanDatabase {
+        let mut sub: CanDatabase = CanDatabase {
+            name: self.name.clone(),
+            bustype: self.bustype.clone(),
+            version: self.version.clone(),
+            comment: self.comment.clone(),
+            bit_timing: self.bit_timing,
+            value_tables: self.value_tables.clone(),
+            signal_types: self.signal_types.clone(),
+            attributes: self.attributes.clone(),
+            attr_spec: self.attr_spec.clone(),
+            rel_attr_spec_bu_sg: self.rel_attr_spec_bu_sg.clone(),
+            rel_attr_spec_bu_bo: self.rel_attr_spec_bu_bo.clone(),
+            name_policy: self.name_policy,
+            ..Default::default()
+        };
+
+        // 1) select what survives, keeping the order views
+        let wanted: HashSet<CanMessageKey> = msg_keys.iter().copied().collect();
+        let kept_msgs: Vec<CanMessageKey> = self
+            .messages_order
+            .iter()
+            .copied()
+            .filter(|k| wanted.contains(k) && self.messages.contains_key(*k))
+            .collect();
+        let kept_sigs: Vec<CanSignalKey> = self
+            .signals_order
+            .iter()
+            .copied()
+            .filter(|&k| {
+                self.get_sig_by_key(k)
+                    .is_some_and(|s| wanted.contains(&s.message))
+            })
+            .collect();
+        let mut involved: HashSet<CanNodeKey> = HashSet::new();
+        for message in kept_msgs.iter().filter_map(|&k| self.get_message_by_key(k)) {
+            involved.extend(&message.sender_nodes);
+            involved.extend(&message.receiver_nodes);
+        }
+        for signal in kept_sigs.iter().filter_map(|&k| self.get_sig_by_key(k)) {
+            involved.extend(&signal.receiver_nodes);
+            involved.extend(&signal.sender_nodes);
+        }
+        let kept_nodes: Vec<CanNodeKey> = self
+            .nodes_order
+            .iter()
+            .copied()
+            .filter(|k| involved.contains(k))
+            .collect();
+
+        // 2) allocate the new keys
+        let node_map: HashMap<CanNodeKey, CanNodeKey> = kept_nodes
+            .iter()
+            .map(|&k| (k, sub.nodes.insert(CanNode::default())))
+            .collect();
+        let msg_map: HashMap<CanMessageKey, CanMessageKey> = kept_msgs
+            .iter()
+            .map(|&k| (k, sub.messages.insert(CanMessage::default())))
+            .collect();
+        let sig_map: HashMap<CanSignalKey, CanSignalKey> = kept_sigs
+            .iter()
+            .map(|&k| (k, sub.signals.insert(CanSignal::default())))
+            .collect();
+
+        // 3) copy the entities with remapped references and rebuild the lookups
+        for &old_key in &kept_nodes {
+            let Some(node) = self.get_node_by_key(old_key) else {
+                continue;
+            };
+            let new_key: CanNodeKey = node_map[&old_key];
+            sub.nodes[new_key] = CanNode {
+                messages_sent: remap_keys(&node.messages_sent, &msg_map),
+                tx_signals: remap_keys(&node.tx_signals, &sig_map),
+                rx_signals: remap_keys(&node.rx_signals, &sig_map),
+                ..node.clone()
+            };
+            sub.nodes_order.push(new_key);
+            sub.node_key_by_name
+                .insert(node.name.to_ascii_lowercase(), new_key);
+        }
+
+        for &old_key in &kept_msgs {
+            let Some(message) = self.get_message_by_key(old_key) else {
+                continue;
+            };
+            let new_key: CanMessageKey = msg_map[&old_key];
+            let mux_cases: HashMap<CanSignalKey, HashMap<MuxSelector, Vec<CanSignalKey>>> = message
+                .mux_cases
+                .iter()
+                .filter_map(|(switch, cases)| {
+                    let cases: HashMap<MuxSelector, Vec<CanSignalKey>> = cases
+                        .iter()
+                        .map(|(sel, sigs)| (sel.clone(), remap_keys(sigs, &sig_map)))
+                        .collect();
+                    sig_map.get(switch).map(|&k| (k, cases))
+                })
+                .collect();
+            sub.messages[new_key] = CanMessage {
+                sender_nodes: remap_keys(&message.sender_nodes, &node_map),
+                receiver_nodes: remap_keys(&message.receiver_nodes, &node_map),
+                signals: remap_keys(&message.signals, &sig_map),
+                mux_multiplexors: remap_keys(&message.mux_multiplexors, &sig_map),
+                checksum_signal: message
+                    .checksum_signal
+                    .and_then(|k| sig_map.get(&k).copied()),
+                counter_signal: message
+                    .counter_signal
+                    .and_then(|k| sig_map.get(&k).copied()),
+                mux_cases,
+                ..message.clone()
+            };
+            sub.messages_order.push(new_key);
+            sub.msg_key_by_id.insert(message.id, new_key);
+            sub.msg_key_by_hex.insert(message.id_hex.clone(), new_key);
+            sub.msg_key_by_name
+                .insert(message.name.to_ascii_lowercase(), new_key);
+        }
+
+        for &old_key in &kept_sigs {
+            let Some(signal) = self.get_sig_by_key(old_key) else {
+                continue;
+            };
+            let new_key: CanSignalKey = sig_map[&old_key];
+            sub.signals[new_key] = CanSignal {
+                message: msg_map[&signal.message],
+                receiver_nodes: remap_keys(&signal.receiver_nodes, &node_map),
+                sender_nodes: remap_keys(&signal.sender_nodes, &node_map),
+                mux_switch: signal.mux_switch.and_then(|k| sig_map.get(&k).copied()),
+                ..signal.clone()
+            };
+            sub.signals_order.push(new_key);
+            sub.sig_key_by_name
+                .insert(signal.name.to_ascii_lowercase(), new_key);
+        }
+
+        // 4) relation attributes whose both ends survived
+        for ((node_key, sig_key), attrs) in &self.bu_sg_rel_attributes {
+            if let (Some(&nk), Some(&sk)) = (node_map.get(node_key), sig_map.get(sig_key)) {
+                sub.bu_sg_rel_attributes.insert((nk, sk), attrs.clone());
+            }
+        }
+        for ((node_key, msg_key), attrs) in &self.bu_bo_rel_attributes {
+            if let (Some(&nk), Some(&mk)) = (node_map.get(node_key), msg_map.get(msg_key)) {
+                sub.bu_bo_rel_attributes.insert((nk, mk), attrs.clone());
+            }
+        }
+
+        sub
+    }
+
+    /// Builds one [`CanDatabase::subset`] per transmitting node, keyed by node name, holding
+    /// the messages that node sends.
+    ///
+    /// Messages with several senders appear in each sender's database; nodes that send
+    /// nothing get no entry.
+    pub fn split_by_sender(&self) -> BTreeMap<String, CanDatabase> {
+        self.nodes_order
+            .iter()
+            .filter_map(|&k| self.get_node_by_key(k))
+            .filter(|node| !node.messages_sent.is_empty())
+            .map(|node| (node.name.clone(), self.subset(&node.messages_sent)))
+            .collect()
+    }
+
+    // -------------- Compaction ---------------
+    /// Rebuilds the node, message and signal arenas with only their live entries, releasing
+    /// the slots left behind by deletions.
+    ///
+    /// Every key is regenerated and all references (order views, relations, lookups,
+    /// multiplexing, checksum/counter, relation attributes) are remapped; dangling keys are
+    /// dropped. The returned [`KeyRemap`] translates keys held by the caller.
+    pub fn compact(&mut self) -> KeyRemap {
+        let old_nodes: SlotMap<CanNodeKey, CanNode> = std::mem::take(&mut self.nodes);
+        let old_messages: SlotMap<CanMessageKey, CanMessage> = std::mem::take(&mut self.messages);
+        let old_signals: SlotMap<CanSignalKey, CanSignal> = std::mem::take(&mut self.signals);
+
+        // 1) allocate the new keys
+        let mut remap: KeyRemap = KeyRemap::default();
+        for (old_key, _) in old_nodes.iter() {
+            remap
+                .nodes
+                .insert(old_key, self.nodes.insert(CanNode::default()));
+        }
+        for (old_key, _) in old_messages.iter() {
+            remap
+                .messages
+                .insert(old_key, self.messages.insert(CanMessage::default()));
+        }
+        for (old_key, _) in old_signals.iter() {
+            remap
+                .signals
+                .insert(old_key, self.signals.insert(CanSignal::default()));
+        }
+        let node_map: &HashMap<CanNodeKey, CanNodeKey> = &remap.nodes;
+        let msg_map: &HashMap<CanMessageKey, CanMessageKey> = &remap.messages;
+        let sig_map: &HashMap<CanSignalKey, CanSignalKey> = &remap.signals;
+
+        // 2) move the entities with remapped references
+        for (old_key, node) in old_nodes {
+            self.nodes[node_map[&old_key]] = CanNode {
+                messages_sent: remap_keys(&node.messages_sent, msg_map),
+                tx_signals: remap_keys(&node.tx_signals, sig_map),
+                rx_signals: remap_keys(&node.rx_signals, sig_map),
+                ..node
+            };
+        }
+        for (old_key, message) in old_messages {
+            let mux_cases: HashMap<CanSignalKey, HashMap<MuxSelector, Vec<CanSignalKey>>> = message
+                .mux_cases
+                .iter()
+                .filter_map(|(switch, cases)| {
+                    let cases: HashMap<MuxSelector, Vec<CanSignalKey>> = cases
+                        .iter()
+                        .map(|(sel, sigs)| (sel.clone(), remap_keys(sigs, sig_map)))
+                        .collect();
+                    sig_map.get(switch).map(|&k| (k, cases))
+                })
+                .collect();
+            self.messages[msg_map[&old_key]] = CanMessage {
+                sender_nodes: remap_keys(&message.sender_nodes, node_map),
+                receiver_nodes: remap_keys(&message.receiver_nodes, node_map),
+                signals: remap_keys(&message.signals, sig_map),
+                mux_multiplexors: remap_keys(&message.mux_multiplexors, sig_map),
+                checksum_signal: message
+                    .checksum_signal
+                    .and_then(|k| sig_map.get(&k).copied()),
+                counter_signal: message
+                    .counter_signal
+                    .and_then(|k| sig_map.get(&k).copied()),
+                mux_cases,
+                ..message
+            };
+        }
+        for (old_key, signal) in old_signals {
+            self.signals[sig_map[&old_key]] = CanSignal {
+                message: msg_map
+                    .get(&signal.message)
+                    .copied()
+                    .unwrap_or_else(CanMessageKey::null),
+                receiver_nodes: remap_keys(&signal.receiver_nodes, node_map),
+                sender_nodes: remap_keys(&signal.sender_nodes, node_map),
+                mux_switch: signal.mux_switch.and_then(|k| sig_map.get(&k).copied()),
+                ..signal
+            };
+        }
+
+        // 3) order views, lookups and relation attributes
+        self.nodes_order = remap_keys(&self.nodes_order, node_map);
+        self.messages_order = remap_keys(&self.messages_order, msg_map);
+        self.signals_order = remap_keys(&self.signals_order, sig_map);
+        self.node_key_by_name
+            .retain(|_, k| node_map.get(k).map(|&new| *k = new).is_some());
+        self.msg_key_by_id
+            .retain(|_, k| msg_map.get(k).map(|&new| *k = new).is_some());
+        self.msg_key_by_hex
+            .retain(|_, k| msg_map.get(k).map(|&new| *k = new).is_some());
+        self.msg_key_by_name
+            .retain(|_, k| msg_map.get(k).map(|&new| *k = new).is_some());
+        self.sig_key_by_name
+            .retain(|_, k| sig_map.get(k).map(|&new| *k = new).is_some());
+        self.bu_sg_rel_attributes = std::mem::take(&mut self.bu_sg_rel_attributes)
+            .into_iter()
+            .filter_map(|((nk, sk), attrs)| {
+                Some(((*node_map.get(&nk)?, *sig_map.get(&sk)?), attrs))
+            })
+            .collect();
+        self.bu_bo_rel_attributes = std::mem::take(&mut self.bu_bo_rel_attributes)
+            .into_iter()
+            .filter_map(|((nk, mk), attrs)| {
+                Some(((*node_map.get(&nk)?, *msg_map.get(&mk)?), attrs))
+            })
+            .collect();
+        self.current_msg = self.current_msg.and_then(|k| msg_map.get(&k).copied());
+
+        remap
+    }
 
     // -------------- Mutable Closures ---------------
     /// Closure to edit all CanNode
@@ -1331,17 +3206,68 @@ impl CanDatabase {
         }
     }
 
-    /// Closure to edit all CanSignal
-    pub fn for_each_signal_mut(&mut self, mut f: impl FnMut(&mut CanSignal)) {
-        let keys = self.signals_order.clone();
-        for k in keys {
-            if let Some(sig) = self.signals.get_mut(k) {
-                f(sig);
-            }
+    /// Closure to edit all CanSignal
+    pub fn for_each_signal_mut(&mut self, mut f: impl FnMut(&mut CanSignal)) {
+        let keys = self.signals_order.clone();
+        for k in keys {
+            if let Some(sig) = self.signals.get_mut(k) {
+                f(sig);
+            }
+        }
+    }
+
+    /// Edits the message `msg_key` through `f`, which also receives a read-only
+    /// [`DatabaseView`] of the rest of the database for cross-reads.
+    ///
+    /// Changing `id` or `name` inside `f` does not update the lookup maps. If `f` panics,
+    /// the message is put back before the panic resumes.
+    pub fn with_message_mut<R>(
+        &mut self,
+        msg_key: CanMessageKey,
+        f: impl FnOnce(&mut CanMessage, &DatabaseView) -> R,
+    ) -> Result<R, DatabaseError> {
+        // move the message out so the rest of the database can be borrowed immutably
+        let Some(slot) = self.messages.get_mut(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+        let mut message: CanMessage = std::mem::take(slot);
+
+        let view: DatabaseView = DatabaseView {
+            db: self,
+            editing: msg_key,
+        };
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| f(&mut message, &view)));
+
+        if let Some(slot) = self.messages.get_mut(msg_key) {
+            *slot = message;
+        }
+        match outcome {
+            Ok(result) => Ok(result),
+            Err(payload) => panic::resume_unwind(payload),
         }
     }
 
     // -------------- Attribute Definition ---------------
+    /// Iterates every attribute definition, entity-level first, then the
+    /// `BU_SG_REL_` and `BU_BO_REL_` relation definitions, each tagged with its scope.
+    pub fn iter_attribute_specs(&self) -> impl Iterator<Item = (&str, AttrScope, &AttributeSpec)> {
+        let objects = self
+            .attr_spec
+            .iter()
+            .map(|(name, spec)| (name.as_str(), AttrScope::Object(spec.type_of_object), spec));
+        let node_signal = self
+            .rel_attr_spec_bu_sg
+            .iter()
+            .map(|(name, spec)| (name.as_str(), AttrScope::NodeSignal, spec));
+        let node_message = self
+            .rel_attr_spec_bu_bo
+            .iter()
+            .map(|(name, spec)| (name.as_str(), AttrScope::NodeMessage, spec));
+        objects.chain(node_signal).chain(node_message)
+    }
+
     /// Registers a new attribute specification on the database.
     pub fn add_attribute_definition(&mut self, spec: AttributeSpec) -> Result<(), DatabaseError> {
         if let Some(existing) = self.attr_spec.get(&spec.name)
@@ -1401,6 +3327,28 @@ impl CanDatabase {
         Ok(())
     }
 
+    /// Defines the attribute `name` on `object` from `def`, with `default` as its
+    /// `BA_DEF_DEF_` value, and assigns the default to every existing entity of that kind.
+    ///
+    /// The default must match the definition (type, bounds, enum entries), otherwise
+    /// [`DatabaseError::AttributeTypeMismatch`] is returned.
+    pub fn define_attribute(
+        &mut self,
+        name: &str,
+        object: AttrObject,
+        def: AttributeDef,
+        default: AttributeValue,
+    ) -> Result<(), DatabaseError> {
+        let spec: AttributeSpec = def.to_spec(name, object, default);
+        if !Self::attribute_value_matches_spec(&spec.default, &spec) {
+            return Err(DatabaseError::AttributeTypeMismatch {
+                name: name.to_string(),
+                value: spec.default.to_string(),
+            });
+        }
+        self.add_attribute_definition(spec)
+    }
+
     /// Edit an already existing attribute
     pub fn edit_attribute_definition(
         &mut self,
@@ -1476,14 +3424,13 @@ impl CanDatabase {
         match (value, spec.value_type) {
             (AttributeValue::Str(_), AttrValueType::String) => true,
             (AttributeValue::Int(v), AttrValueType::Int) => {
-                spec.int_min.is_none_or(|min| *v >= min) && spec.int_max.is_none_or(|max| *v <= max)
+                Self::value_within_bounds(*v, spec.int_min, spec.int_max)
             }
             (AttributeValue::Hex(v), AttrValueType::Hex) => {
-                spec.hex_min.is_none_or(|min| *v >= min) && spec.hex_max.is_none_or(|max| *v <= max)
+                Self::value_within_bounds(*v, spec.hex_min, spec.hex_max)
             }
             (AttributeValue::Float(v), AttrValueType::Float) => {
-                spec.float_min.is_none_or(|min| *v >= min)
-                    && spec.float_max.is_none_or(|max| *v <= max)
+                Self::value_within_bounds(*v, spec.float_min, spec.float_max)
             }
             (AttributeValue::Enum(v), AttrValueType::Enum) => {
                 spec.enum_values.iter().any(|entry| entry == v)
@@ -1492,6 +3439,15 @@ impl CanDatabase {
         }
     }
 
+    /// Checks `min <= v <= max`, tolerating specs whose bounds are reversed.
+    fn value_within_bounds<T: PartialOrd + Copy>(v: T, min: Option<T>, max: Option<T>) -> bool {
+        let (min, max) = match (min, max) {
+            (Some(lo), Some(hi)) if lo > hi => (Some(hi), Some(lo)),
+            bounds => bounds,
+        };
+        min.is_none_or(|min| v >= min) && max.is_none_or(|max| v <= max)
+    }
+
     fn reconcile_attribute_entry(
         map: &mut BTreeMap<String, AttributeValue>,
         old_name: &str,
@@ -1569,6 +3525,333 @@ impl CanDatabase {
         Ok(())
     }
 
+    // -------------- Attribute Values ---------------
+    /// Sets a database attribute after validating `value` against its definition.
+    pub fn set_database_attribute(
+        &mut self,
+        name: &str,
+        value: AttributeValue,
+    ) -> Result<(), DatabaseError> {
+        self.check_attribute_value(name, &value, AttrObject::Database)?;
+        self.attributes.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Sets the bus type and its `BusType` database attribute, defining it when missing.
+    pub fn set_bus_type(&mut self, bustype: BusType) {
+        self.attr_spec
+            .entry("BusType".to_string())
+            .or_insert_with(|| AttributeSpec {
+                type_of_object: AttrObject::Database,
+                default: AttributeValue::Str("".to_string()),
+                name: "BusType".to_string(),
+                value_type: AttrValueType::String,
+                ..Default::default()
+            });
+        self.attributes
+            .insert("BusType".to_string(), AttributeValue::Str(bustype.to_str()));
+        self.bustype = bustype;
+    }
+
+    /// Sets the classic CAN (arbitration) baud rate, stored in `BA_ "Baudrate"`.
+    pub fn set_baudrate(&mut self, baudrate: u32) {
+        self.set_baudrate_attribute("Baudrate", baudrate);
+    }
+
+    /// Sets the CAN FD data-phase baud rate, stored in `BA_ "BaudrateCANFD"`.
+    pub fn set_baudrate_canfd(&mut self, baudrate: u32) {
+        self.set_baudrate_attribute("BaudrateCANFD", baudrate);
+    }
+
+    /// Returns the `Baudrate` database attribute, if set.
+    pub fn baudrate(&self) -> Option<u32> {
+        self.baudrate_attribute("Baudrate")
+    }
+
+    /// Returns the `BaudrateCANFD` database attribute, if set.
+    pub fn baudrate_canfd(&self) -> Option<u32> {
+        self.baudrate_attribute("BaudrateCANFD")
+    }
+
+    /// Reads a baud rate database attribute as `u32`.
+    fn baudrate_attribute(&self, name: &str) -> Option<u32> {
+        match self.attributes.get(name)? {
+            AttributeValue::Int(v) => u32::try_from(*v).ok(),
+            AttributeValue::Hex(v) => u32::try_from(*v).ok(),
+            AttributeValue::Float(v) => Some(*v as u32),
+            _ => None,
+        }
+    }
+
+    /// Stores a baud rate in the `Baudrate`/`BaudrateCANFD` database attribute,
+    /// defining it first (with the bounds used by `create::new_database`) when missing.
+    pub(crate) fn set_baudrate_attribute(&mut self, name: &str, value: u32) {
+        let int_max: i64 = if name == "BaudrateCANFD" {
+            16_000_000
+        } else {
+            1_000_000
+        };
+        self.attr_spec
+            .entry(name.to_string())
+            .or_insert_with(|| AttributeSpec {
+                type_of_object: AttrObject::Database,
+                default: AttributeValue::Int(500_000),
+                name: name.to_string(),
+                value_type: AttrValueType::Int,
+                int_min: Some(1),
+                int_max: Some(int_max),
+                ..Default::default()
+            });
+        self.attributes
+            .insert(name.to_string(), AttributeValue::Int(value as i64));
+    }
+
+    /// Removes a database attribute value, returning it if it was set.
+    pub fn remove_database_attribute(&mut self, name: &str) -> Option<AttributeValue> {
+        self.attributes.remove(name)
+    }
+
+    /// Sets a node attribute after validating `value` against its definition.
+    pub fn set_node_attribute(
+        &mut self,
+        node_key: CanNodeKey,
+        name: &str,
+        value: AttributeValue,
+    ) -> Result<(), DatabaseError> {
+        self.check_attribute_value(name, &value, AttrObject::Node)?;
+        let Some(node) = self.get_node_by_key_mut(node_key) else {
+            return Err(DatabaseError::NodeMissing { node_key });
+        };
+        node.attributes.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes a node attribute value, returning it if it was set.
+    pub fn remove_node_attribute(
+        &mut self,
+        node_key: CanNodeKey,
+        name: &str,
+    ) -> Result<Option<AttributeValue>, DatabaseError> {
+        let Some(node) = self.get_node_by_key_mut(node_key) else {
+            return Err(DatabaseError::NodeMissing { node_key });
+        };
+        Ok(node.attributes.remove(name))
+    }
+
+    /// Sets a message attribute after validating `value` against its definition.
+    pub fn set_message_attribute(
+        &mut self,
+        msg_key: CanMessageKey,
+        name: &str,
+        value: AttributeValue,
+    ) -> Result<(), DatabaseError> {
+        self.check_attribute_value(name, &value, AttrObject::Message)?;
+        let Some(message) = self.get_message_by_key_mut(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+        message.attributes.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes a message attribute value, returning it if it was set.
+    pub fn remove_message_attribute(
+        &mut self,
+        msg_key: CanMessageKey,
+        name: &str,
+    ) -> Result<Option<AttributeValue>, DatabaseError> {
+        let Some(message) = self.get_message_by_key_mut(msg_key) else {
+            return Err(DatabaseError::MessageMissing {
+                message_key: msg_key,
+            });
+        };
+        Ok(message.attributes.remove(name))
+    }
+
+    /// Sets a signal attribute after validating `value` against its definition.
+    pub fn set_signal_attribute(
+        &mut self,
+        sig_key: CanSignalKey,
+        name: &str,
+        value: AttributeValue,
+    ) -> Result<(), DatabaseError> {
+        self.check_attribute_value(name, &value, AttrObject::Signal)?;
+        let Some(signal) = self.get_sig_by_key_mut(sig_key) else {
+            return Err(DatabaseError::SignalMissing {
+                signal_key: sig_key,
+            });
+        };
+        signal.attributes.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes a signal attribute value, returning it if it was set.
+    pub fn remove_signal_attribute(
+        &mut self,
+        sig_key: CanSignalKey,
+        name: &str,
+    ) -> Result<Option<AttributeValue>, DatabaseError> {
+        let Some(signal) = self.get_sig_by_key_mut(sig_key) else {
+            return Err(DatabaseError::SignalMissing {
+                signal_key: sig_key,
+            });
+        };
+        Ok(signal.attributes.remove(name))
+    }
+
+    /// Sets a `BU_SG_REL_` attribute on the (node, signal) pair resolved by name, after
+    /// validating `value` against its `BA_DEF_REL_` definition.
+    ///
+    /// A name that does not resolve yields `NodeNameNotFound`/`SignalNameNotFound`.
+    pub fn set_bu_sg_rel_attribute_by_name(
+        &mut self,
+        node: &str,
+        signal: &str,
+        name: &str,
+        value: AttributeValue,
+    ) -> Result<(), DatabaseError> {
+        let Some(node_key) = self.get_node_key_by_name(node) else {
+            return Err(DatabaseError::NodeNameNotFound {
+                name: node.to_string(),
+            });
+        };
+        let Some(sig_key) = self.get_sig_key_by_name(signal) else {
+            return Err(DatabaseError::SignalNameNotFound {
+                name: signal.to_string(),
+            });
+        };
+        let Some(spec) = self.rel_attr_spec_bu_sg.get(name) else {
+            return Err(DatabaseError::RelationAttributeNotFound {
+                name: name.to_string(),
+                scope: AttrScope::NodeSignal,
+            });
+        };
+        if !Self::attribute_value_matches_spec(&value, spec) {
+            return Err(DatabaseError::AttributeTypeMismatch {
+                name: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+        self.bu_sg_rel_attributes
+            .entry((node_key, sig_key))
+            .or_default()
+            .insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Checks that `name` is defined for `scope` and that `value` matches its type, bounds
+    /// and (for ENUM) its entries.
+    fn check_attribute_value(
+        &self,
+        name: &str,
+        value: &AttributeValue,
+        scope: AttrObject,
+    ) -> Result<(), DatabaseError> {
+        let Some(spec) = self
+            .attr_spec
+            .get(name)
+            .filter(|spec| spec.type_of_object == scope)
+        else {
+            return Err(DatabaseError::AttributeNotFound {
+                name: name.to_string(),
+                scope,
+            });
+        };
+        if !Self::attribute_value_matches_spec(value, spec) {
+            return Err(DatabaseError::AttributeTypeMismatch {
+                name: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    // -------------- Comparison ---------------
+    /// Compares two databases by content, ignoring key identity and ordering.
+    ///
+    /// Nodes are matched by name (comment and attributes must agree), messages by ID (name,
+    /// payload length, comment, attributes and transmitters must agree) and signals by name
+    /// within their message (layout, scaling, range, unit, receivers, multiplexing, comment,
+    /// attributes, value table and signal type must agree). Signals without a message are matched by name.
+    pub fn semantically_equals(&self, other: &CanDatabase) -> bool {
+        if self.nodes.len() != other.nodes.len()
+            || self.messages.len() != other.messages.len()
+            || self.signals.len() != other.signals.len()
+        {
+            return false;
+        }
+
+        let nodes_equal: bool = self.iter_nodes().all(|node| {
+            other.get_node_by_name(&node.name).is_some_and(|o| {
+                o.name == node.name && o.comment == node.comment && o.attributes == node.attributes
+            })
+        });
+        let messages_equal: bool = self.iter_messages().all(|message| {
+            other
+                .get_message_by_id(message.id)
+                .is_some_and(|o| self.message_equals(message, other, o))
+        });
+        let orphans_equal: bool =
+            self.iter_signals()
+                .filter(|s| s.message.is_null())
+                .all(|signal| {
+                    other
+                        .iter_signals()
+                        .filter(|s| s.message.is_null())
+                        .any(|o| o.name == signal.name && self.signal_equals(signal, other, o))
+                });
+
+        nodes_equal && messages_equal && orphans_equal
+    }
+
+    /// Content comparison of a message of `self` with a message of `other`.
+    fn message_equals(&self, a: &CanMessage, other: &CanDatabase, b: &CanMessage) -> bool {
+        a.name == b.name
+            && a.byte_length == b.byte_length
+            && a.comment == b.comment
+            && a.attributes == b.attributes
+            && self.node_names(&a.sender_nodes) == other.node_names(&b.sender_nodes)
+            && a.signals.len() == b.signals.len()
+            && a.signals(self).all(|signal| {
+                b.signals(other)
+                    .find(|o| o.name == signal.name)
+                    .is_some_and(|o| self.signal_equals(signal, other, o))
+            })
+    }
+
+    /// Content comparison of a signal of `self` with a signal of `other`.
+    fn signal_equals(&self, a: &CanSignal, other: &CanDatabase, b: &CanSignal) -> bool {
+        a.name == b.name
+            && a.bit_start == b.bit_start
+            && a.bit_length == b.bit_length
+            && a.endian == b.endian
+            && a.sign == b.sign
+            && a.factor == b.factor
+            && a.offset == b.offset
+            && a.min == b.min
+            && a.max == b.max
+            && a.unit_of_measurement == b.unit_of_measurement
+            && a.comment == b.comment
+            && a.attributes == b.attributes
+            && a.value_table == b.value_table
+            && a.signal_type == b.signal_type
+            && a.mux_role == b.mux_role
+            && (a.mux_role != MuxRole::Multiplexed || a.mux_selector == b.mux_selector)
+            && self.node_names(&a.receiver_nodes) == other.node_names(&b.receiver_nodes)
+    }
+
+    /// Sorted names of the nodes in `keys`, skipping dangling keys.
+    fn node_names(&self, keys: &[CanNodeKey]) -> Vec<&str> {
+        let mut names: Vec<&str> = keys
+            .iter()
+            .filter_map(|&k| self.get_node_by_key(k))
+            .map(|n| n.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
     // -------------- Sorting ---------------
     /// Sort nodes_by_name case insensitive
     pub fn sort_db_nodes_by_name(&mut self) {
@@ -1588,6 +3871,41 @@ impl CanDatabase {
             .sort_by_cached_key(|&k| self.signals.get(k).map(|s| s.name.to_ascii_lowercase()));
     }
 
+    /// Reorders `nodes_order` by `key` (names compare case-insensitively).
+    pub fn sort_nodes_by(&mut self, key: NodeSortKey) {
+        self.sort_db_nodes_by_name();
+        if key == NodeSortKey::NameDesc {
+            self.nodes_order.reverse();
+        }
+    }
+
+    /// Reorders `messages_order` by `key`; ties on ID or payload length are broken by name.
+    pub fn sort_messages_by(&mut self, key: MessageSortKey) {
+        self.sort_db_messages_by_name();
+        match key {
+            MessageSortKey::NameAsc => {}
+            MessageSortKey::NameDesc => self.messages_order.reverse(),
+            MessageSortKey::IdAsc => self
+                .messages_order
+                .sort_by_key(|&k| self.messages.get(k).map(|m| m.id)),
+            MessageSortKey::IdDesc => self
+                .messages_order
+                .sort_by_key(|&k| std::cmp::Reverse(self.messages.get(k).map(|m| m.id))),
+            MessageSortKey::DlcAsc => self
+                .messages_order
+                .sort_by_key(|&k| self.messages.get(k).map(|m| m.byte_length)),
+        }
+    }
+
+    /// Reorders `signals_order` by `key`; ties on start bit are broken by name.
+    pub fn sort_signals_by(&mut self, key: SignalSortKey) {
+        self.sort_db_signals_by_name();
+        if key == SignalSortKey::StartBitAsc {
+            self.signals_order
+                .sort_by_key(|&k| self.signals.get(k).map(|s| s.bit_start));
+        }
+    }
+
     /// Sort `messages_sent`, `tx_signals` and `rx_signals` inside the specific given CanNode
     /// by the target names (ASCII case-insensitive).
     pub fn sort_node_fields(&mut self, node_key: CanNodeKey) {
@@ -1893,6 +4211,191 @@ impl BusType {
             BusType::CanFd => "CAN FD".to_string(),
         }
     }
+
+    /// Reads a `BA_ "BusType"` value: `"CAN FD"` is CAN FD, anything else classic CAN.
+    pub(crate) fn from_label(label: &str) -> BusType {
+        match label {
+            "CAN FD" => BusType::CanFd,
+            _ => BusType::Can,
+        }
+    }
+}
+
+/// Naming rule applied to node, message and signal names on insert.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NamePolicy {
+    /// Any name is accepted (historical behavior).
+    #[default]
+    Lenient,
+    /// Names must be C identifiers: `[A-Za-z_][A-Za-z0-9_]*`.
+    CIdentifier,
+}
+
+impl NamePolicy {
+    /// Returns `true` if `name` satisfies this policy.
+    pub fn allows(&self, name: &str) -> bool {
+        match self {
+            NamePolicy::Lenient => true,
+            NamePolicy::CIdentifier => {
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+        }
+    }
+
+    /// Rewrites `name` into a valid DBC identifier.
+    ///
+    /// Every character outside `[A-Za-z0-9_]` becomes `_`; under `CIdentifier` a leading
+    /// digit (or an empty name) is additionally prefixed with `_`.
+    pub fn sanitize(&self, name: &str) -> String {
+        let mut out: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if *self == NamePolicy::CIdentifier
+            && !out.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        {
+            out.insert(0, '_');
+        }
+        out
+    }
+}
+
+impl fmt::Display for NamePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamePolicy::Lenient => f.write_str("Lenient"),
+            NamePolicy::CIdentifier => f.write_str("C identifier"),
+        }
+    }
+}
+
+/// Ordering applied to `nodes_order` by [`CanDatabase::sort_nodes_by`].
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NodeSortKey {
+    #[default]
+    NameAsc,
+    NameDesc,
+}
+
+/// Ordering applied to `messages_order` by [`CanDatabase::sort_messages_by`].
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MessageSortKey {
+    #[default]
+    NameAsc,
+    NameDesc,
+    IdAsc,
+    IdDesc,
+    /// Payload length, ascending.
+    DlcAsc,
+}
+
+/// Ordering applied to `signals_order` by [`CanDatabase::sort_signals_by`].
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SignalSortKey {
+    #[default]
+    NameAsc,
+    StartBitAsc,
+}
+
+/// Old → new key translation, as returned by [`CanDatabase::compact`].
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct KeyRemap {
+    /// Node keys before → after compaction.
+    pub nodes: HashMap<CanNodeKey, CanNodeKey>,
+    /// Message keys before → after compaction.
+    pub messages: HashMap<CanMessageKey, CanMessageKey>,
+    /// Signal keys before → after compaction.
+    pub signals: HashMap<CanSignalKey, CanSignalKey>,
+}
+
+/// Cleanup candidates, as returned by [`CanDatabase::unused`].
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct UnusedReport {
+    /// Nodes without sent messages, transmitted signals or received signals.
+    pub nodes: Vec<CanNodeKey>,
+    /// Signals not bound to any message.
+    pub orphan_signals: Vec<CanSignalKey>,
+    /// `(signal, raw)` value-table entries outside the signal's raw range.
+    pub value_table_out_of_range: Vec<(CanSignalKey, i32)>,
+}
+
+/// Bit timing parameters of the `BS_: <baudrate>:<BTR1>,<BTR2>` section.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BitTiming {
+    /// Baudrate in bit/s.
+    pub baudrate: u32,
+    /// Value of the Bit Timing Register 1.
+    pub btr1: u8,
+    /// Value of the Bit Timing Register 2.
+    pub btr2: u8,
+}
+
+impl fmt::Display for BitTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{},{}", self.baudrate, self.btr1, self.btr2)
+    }
+}
+
+/// Read-only view of a database handed out by [`CanDatabase::with_message_mut`].
+///
+/// The message being edited is hidden: lookups for its key return `None`.
+pub struct DatabaseView<'a> {
+    db: &'a CanDatabase,
+    editing: CanMessageKey,
+}
+
+impl<'a> DatabaseView<'a> {
+    /// Returns a node given its key.
+    pub fn get_node_by_key(&self, key: CanNodeKey) -> Option<&'a CanNode> {
+        self.db.get_node_by_key(key)
+    }
+
+    /// Returns a message given its key (`None` for the message being edited).
+    pub fn get_message_by_key(&self, key: CanMessageKey) -> Option<&'a CanMessage> {
+        if key == self.editing {
+            return None;
+        }
+        self.db.get_message_by_key(key)
+    }
+
+    /// Returns a signal given its key.
+    pub fn get_sig_by_key(&self, key: CanSignalKey) -> Option<&'a CanSignal> {
+        self.db.get_sig_by_key(key)
+    }
+
+    /// Iterates nodes following `nodes_order`.
+    pub fn iter_nodes(&self) -> impl Iterator<Item = &'a CanNode> + 'a {
+        self.db.iter_nodes()
+    }
+
+    /// Iterates the other messages following `messages_order`.
+    pub fn iter_messages(&self) -> impl Iterator<Item = &'a CanMessage> + 'a {
+        let (db, editing) = (self.db, self.editing);
+        db.messages_order
+            .iter()
+            .filter(move |&&k| k != editing)
+            .filter_map(move |&k| db.messages.get(k))
+    }
+
+    /// Iterates signals following `signals_order`.
+    pub fn iter_signals(&self) -> impl Iterator<Item = &'a CanSignal> + 'a {
+        self.db.iter_signals()
+    }
+
+    /// Returns the attribute specifications of the database.
+    pub fn attr_spec(&self) -> &'a BTreeMap<String, AttributeSpec> {
+        &self.db.attr_spec
+    }
 }
 
 // suport struct for node parsing
@@ -1915,8 +4418,58 @@ type MessageFieldPlan = (
 );
 
 const CAN_EFF_MASK: u32 = 0x1FFF_FFFF; // 29 bit
+const DBC_EXTENDED_ID_FLAG: u32 = 0x8000_0000; // bit 31 marks extended IDs in BO_
 const CAN_SFF_MASK: u32 = 0x0000_07FF; // 11 bit
 
+/// Returns the mask of the raw values representable by `signal` (all ones up to 64 bits).
+fn raw_mask(signal: &CanSignal) -> u64 {
+    if signal.bit_length >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << signal.bit_length) - 1
+    }
+}
+
+/// Returns the sanitized form of `old`, made unique against `taken` (lowercase names),
+/// or `None` when `old` is already clean. The old name is released from `taken`.
+fn unique_sanitized(old: &str, policy: NamePolicy, taken: &mut HashSet<String>) -> Option<String> {
+    let base: String = policy.sanitize(old);
+    if base == old {
+        return None;
+    }
+    taken.remove(&old.to_ascii_lowercase());
+    let mut candidate: String = base.clone();
+    let mut n: u32 = 2;
+    while taken.contains(&candidate.to_ascii_lowercase()) {
+        candidate = format!("{base}_{n}");
+        n += 1;
+    }
+    taken.insert(candidate.to_ascii_lowercase());
+    Some(candidate)
+}
+
+/// Maps `keys` through `map`, dropping the keys that have no counterpart.
+fn remap_keys<K: Key + std::hash::Hash>(keys: &[K], map: &HashMap<K, K>) -> Vec<K> {
+    keys.iter().filter_map(|k| map.get(k).copied()).collect()
+}
+
+/// Normalizes a user-supplied hexadecimal CAN ID (`"12dd54e3"`, `"0x12dd54e3"`,
+/// `"12DD54E3x"`, ...) to the `id_to_hex` form (`"0x12DD54E3"`).
+///
+/// Input that is not a valid hexadecimal `u32` is returned trimmed and uppercased.
+pub fn normalize_id_hex(input: &str) -> String {
+    let trimmed: &str = input.trim();
+    let digits: &str = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    let digits: &str = digits.trim_end_matches(['x', 'X']);
+    match u32::from_str_radix(digits, 16) {
+        Ok(id) => id_to_hex(id),
+        Err(_) => trimmed.to_ascii_uppercase(),
+    }
+}
+
 #[inline]
 pub fn id_to_hex(id: u32) -> String {
     if id <= CAN_SFF_MASK {
@@ -2,8 +2,8 @@ use std::io;
 use thiserror::Error;
 
 use crate::types::{
-    attributes::AttrObject,
-    database::{CanMessageKey, CanNodeKey, CanSignalKey},
+    attributes::{AttrObject, AttrScope},
+    database::{CanMessageKey, CanNodeKey, CanSignalKey, NamePolicy},
 };
 
 /// Errors produced while parsing a `.dbc` file.
@@ -23,6 +23,16 @@ pub enum DbcParseError {
         #[source]
         source: io::Error,
     },
+    #[error("{} malformed line(s){}", lines.len(), first_malformed(lines))]
+    MalformedLines { lines: Vec<(usize, String)> },
+}
+
+/// Describes the first entry of [`DbcParseError::MalformedLines`], if any.
+fn first_malformed(lines: &[(usize, String)]) -> String {
+    lines
+        .first()
+        .map(|(number, line)| format!(", first at line {number}: {line}"))
+        .unwrap_or_default()
 }
 
 /// Errors produced while creating a new empty `.dbc` file.
@@ -57,8 +67,12 @@ pub enum DbcSaveError {
         #[source]
         source: io::Error,
     },
-    #[error("Failed to format DBC content")]
-    Format,
+    #[error("Failed to format DBC content near '{context}'. \nError: {source}")]
+    Format {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
 }
 
 /// Errors produced while verifying that a signal fits a CAN frame layout.
@@ -82,8 +96,14 @@ pub enum MessageLayoutError {
         total_bits: usize,
         dlc: u16,
     },
-    #[error("Out of bounds (Motorola): Signal linearized  end = {end} < 0 (bytes={dlc})")]
-    MotorolaEndOutOfBounds { end: isize, dlc: u16 },
+    #[error(
+        "Out of bounds (Motorola)! \nSignal linearized  end = {end} \nMessage total bits = {total_bits} (bytes={dlc})"
+    )]
+    MotorolaEndOutOfBounds {
+        end: usize,
+        total_bits: usize,
+        dlc: u16,
+    },
 }
 
 /// Errors returned by high-level operations on [`CanDatabase`](crate::types::database::CanDatabase).
@@ -93,19 +113,36 @@ pub enum DatabaseError {
     NodeAlreadyExists { name: String },
     #[error("Node not found for key {node_key:?}")]
     NodeMissing { node_key: CanNodeKey },
+    #[error("Node '{name}' not found")]
+    NodeNameNotFound { name: String },
     #[error("Message '{name}' already exists")]
     MessageAlreadyExists { name: String },
     #[error("Message ID {id_hex} already assigned to an existing message")]
     MessageIdAlreadyAssigned { id_hex: String },
+    #[error("Name '{name}' is not allowed by the {policy} name policy")]
+    InvalidName { name: String, policy: NamePolicy },
+    #[error("Payload length {byte_length} is not a valid CAN/CAN FD size")]
+    InvalidDlc { byte_length: u16 },
     #[error("Message not found for key {message_key:?}")]
     MessageMissing { message_key: CanMessageKey },
+    #[error("Message '{name}' not found")]
+    MessageNameNotFound { name: String },
     #[error("Signal not found for key {signal_key:?}")]
     SignalMissing { signal_key: CanSignalKey },
+    #[error("Signal '{name}' not found")]
+    SignalNameNotFound { name: String },
     #[error("Signal '{signal}' is already associated with {associated_with}")]
     SignalAlreadyAssociated {
         signal: String,
         associated_with: String,
     },
+    #[error("Signal '{name}' does not fit in a 64-byte payload next to the other signals")]
+    SignalDoesNotFit { name: String },
+    #[error("Signal {incoming:?} overlaps the bits of signal {existing:?}")]
+    SignalOverlap {
+        existing: CanSignalKey,
+        incoming: CanSignalKey,
+    },
     #[error("Value table entry {entry} already exists for signal '{signal}'")]
     ValueTableEntryAlreadyExists { signal: String, entry: String },
     #[error("Value table entry {entry} is not defined for signal '{signal}'")]
@@ -120,6 +157,10 @@ pub enum DatabaseError {
     AttributeAlreadyExists { name: String, scope: AttrObject },
     #[error("Attribute '{name}' not defined for {scope}")]
     AttributeNotFound { name: String, scope: AttrObject },
+    #[error("Relation attribute '{name}' not defined for {scope}")]
+    RelationAttributeNotFound { name: String, scope: AttrScope },
+    #[error("Value '{value}' does not match the definition of attribute '{name}'")]
+    AttributeTypeMismatch { name: String, value: String },
     #[error("Changing the Type of Object is not allowed")]
     AttributeObjectChanging,
     #[error(transparent)]
@@ -35,6 +35,22 @@ pub struct CanMessage {
     pub signals: Vec<CanSignalKey>,
     /// Associated comment (DBC `CM_ BO_` section).
     pub comment: String,
+    /// Frame format from `BA_ "VFrameFormat"`, if present. Overrides the DLC-based `msgtype`.
+    pub frame_format: Option<FrameFormat>,
+    /// Send type from `BA_ "GenMsgSendType"` (e.g. `"Cyclic"`), if present.
+    pub send_type: Option<String>,
+    /// Minimum delay between two transmissions in ms, from `BA_ "GenMsgDelayTime"`.
+    pub delay_time: Option<u32>,
+    /// Transmission period in ms, from `BA_ "GenMsgCycleTime"`.
+    pub cycle_time: Option<u32>,
+    /// Delay before the first transmission in ms, from `BA_ "GenMsgStartDelayTime"`.
+    pub start_delay_time: Option<u32>,
+    /// Repetitions sent on an event, from `BA_ "GenMsgNrOfRepetition"`.
+    pub nr_of_repetition: Option<u32>,
+    /// Signal carrying the frame checksum, written by `CanDatabase::finalize_frame`.
+    pub checksum_signal: Option<CanSignalKey>,
+    /// Rolling counter signal, incremented by `CanDatabase::finalize_frame`.
+    pub counter_signal: Option<CanSignalKey>,
     /// List of multiplexor switch signals (primary first). Empty if none.
     pub mux_multiplexors: Vec<CanSignalKey>,
 
@@ -53,6 +69,30 @@ impl CanMessage {
         *self = CanMessage::default();
     }
 
+    /// Sets the frame format and aligns `msgtype` and `id_format` with it.
+    pub fn set_frame_format(&mut self, frame_format: FrameFormat) {
+        self.msgtype = if frame_format.is_fd() {
+            "CAN FD".into()
+        } else {
+            "CAN".into()
+        };
+        self.id_format = if frame_format.is_extended() {
+            IdFormat::Extended
+        } else {
+            IdFormat::Standard
+        };
+        self.frame_format = Some(frame_format);
+    }
+
+    /// Returns `true` for a CAN FD message: per `VFrameFormat` when set, otherwise per
+    /// `msgtype`.
+    pub fn is_fd(&self) -> bool {
+        match self.frame_format {
+            Some(frame_format) => frame_format.is_fd(),
+            None => self.msgtype != "CAN",
+        }
+    }
+
     /// Convenience iterator over the `CanSignal`s belonging to this message.
     pub fn signals<'a>(&'a self, db: &'a CanDatabase) -> impl Iterator<Item = &'a CanSignal> + 'a {
         self.signals
@@ -79,6 +119,62 @@ impl IdFormat {
     }
 }
 
+/// Frame format as encoded by the `VFrameFormat` message attribute.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FrameFormat {
+    StandardCan,   // 0
+    ExtendedCan,   // 1
+    StandardCanFd, // 14
+    ExtendedCanFd, // 15
+}
+
+impl FrameFormat {
+    /// Maps a `VFrameFormat` value to a frame format (`None` for reserved values).
+    pub fn from_code(code: i64) -> Option<Self> {
+        match code {
+            0 => Some(FrameFormat::StandardCan),
+            1 => Some(FrameFormat::ExtendedCan),
+            14 => Some(FrameFormat::StandardCanFd),
+            15 => Some(FrameFormat::ExtendedCanFd),
+            _ => None,
+        }
+    }
+
+    /// Returns the `VFrameFormat` value of this frame format.
+    pub fn code(&self) -> u8 {
+        match self {
+            FrameFormat::StandardCan => 0,
+            FrameFormat::ExtendedCan => 1,
+            FrameFormat::StandardCanFd => 14,
+            FrameFormat::ExtendedCanFd => 15,
+        }
+    }
+
+    /// Returns `true` for CAN FD frame formats.
+    pub fn is_fd(&self) -> bool {
+        matches!(
+            self,
+            FrameFormat::StandardCanFd | FrameFormat::ExtendedCanFd
+        )
+    }
+
+    /// Returns `true` for extended (29-bit) identifier frame formats.
+    pub fn is_extended(&self) -> bool {
+        matches!(self, FrameFormat::ExtendedCan | FrameFormat::ExtendedCanFd)
+    }
+}
+
+impl fmt::Display for FrameFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameFormat::StandardCan => f.write_str("StandardCAN"),
+            FrameFormat::ExtendedCan => f.write_str("ExtendedCAN"),
+            FrameFormat::StandardCanFd => f.write_str("StandardCAN_FD"),
+            FrameFormat::ExtendedCanFd => f.write_str("ExtendedCAN_FD"),
+        }
+    }
+}
+
 /// Role a signal plays in multiplexing.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub enum MuxRole {
@@ -117,6 +213,31 @@ impl Default for MuxSelector {
     }
 }
 
+impl MuxSelector {
+    /// Returns `true` if the multiplexer switch value `value` selects this case.
+    pub fn matches(&self, value: u64) -> bool {
+        match *self {
+            MuxSelector::Value(v) => value == v as u64,
+            MuxSelector::Range { min, max } => (min as u64..=max as u64).contains(&value),
+        }
+    }
+
+    /// Returns `true` if some switch value is selected by both `self` and `other`.
+    pub fn intersects(&self, other: &MuxSelector) -> bool {
+        let (a_min, a_max) = self.bounds();
+        let (b_min, b_max) = other.bounds();
+        a_min <= b_max && b_min <= a_max
+    }
+
+    /// Returns the inclusive `(min, max)` switch values selected.
+    fn bounds(&self) -> (u32, u32) {
+        match *self {
+            MuxSelector::Value(v) => (v, v),
+            MuxSelector::Range { min, max } => (min, max),
+        }
+    }
+}
+
 impl fmt::Display for MuxSelector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -135,3 +256,16 @@ pub enum GenMsgSendType {
     #[default]
     NoMsgSendType, // 8
 }
+
+/// Payload usage of a message, as returned by `CanDatabase::message_coverage`.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct Coverage {
+    /// Bits used by at least one signal (multiplexed signals counted as their union).
+    pub used_bits: u16,
+    /// Bits of the payload not used by any signal.
+    pub free_bits: u16,
+    /// Payload size in bits (`byte_length * 8`).
+    pub total_bits: u16,
+    /// Contiguous free bit ranges `(first, last)`, inclusive, as `byte * 8 + bit`.
+    pub gaps: Vec<(u16, u16)>,
+}
@@ -1,6 +1,7 @@
 pub mod attributes;
 pub mod database;
 pub mod errors;
+pub mod frame;
 pub mod message;
 pub mod node;
 pub mod signal;
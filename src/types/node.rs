@@ -2,7 +2,11 @@ use crate::types::{
     attributes::AttributeValue,
     database::{CanMessageKey, CanSignalKey},
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Placeholder node name DBC tools write where no node applies (e.g. a signal without
+/// receivers). It never denotes a real node.
+pub const PLACEHOLDER_NODE: &str = "Vector__XXX";
 
 /// Node/ECU defined in the database.
 #[derive(Default, Clone, PartialEq)]
@@ -30,3 +34,28 @@ impl CanNode {
         *self = CanNode::default();
     }
 }
+
+/// Transmit/receive overview of a node, as returned by `CanDatabase::node_summary`.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct NodeSummary {
+    /// Messages transmitted by the node.
+    pub messages_sent: Vec<CanMessageKey>,
+    /// Messages the node receives at least one signal from (deduplicated).
+    pub messages_received: Vec<CanMessageKey>,
+    /// Number of signals transmitted by the node.
+    pub tx_signal_count: usize,
+    /// Number of signals received by the node.
+    pub rx_signal_count: usize,
+    /// Distinct, non-empty units of the received signals.
+    pub rx_units: BTreeSet<String>,
+}
+
+/// Messages sent and received by a node, as returned by
+/// `CanDatabase::messages_touching_node`. Both lists follow `messages_order`.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct NodeMessages {
+    /// Messages the node transmits.
+    pub sent: Vec<CanMessageKey>,
+    /// Messages the node receives at least one signal from.
+    pub received: Vec<CanMessageKey>,
+}
@@ -1,3 +1,4 @@
+use crate::core::strings::format_f64;
 use crate::types::{
     attributes::AttributeValue,
     database::{CanDatabase, CanMessageKey, CanNodeKey, CanSignalKey},
@@ -30,7 +31,8 @@ pub struct CanSignal {
     pub message: CanMessageKey,
     /// Signal name.
     pub name: String,
-    /// Bit start in the payload (bit 0 = LSB of the first byte).
+    /// Bit start in the payload (bit 0 = LSB of the first byte): the LSB of Intel signals,
+    /// the MSB of Motorola signals.
     pub bit_start: u16,
     /// Bit length.
     pub bit_length: u16,
@@ -50,10 +52,15 @@ pub struct CanSignal {
     pub unit_of_measurement: String,
     /// Receiver nodes.
     pub receiver_nodes: Vec<CanNodeKey>,
+    /// Transmitting nodes when they differ from the message senders (e.g. per multiplexed
+    /// group); empty means the message senders transmit the signal.
+    pub sender_nodes: Vec<CanNodeKey>,
     /// Associated comment (DBC `CM_ SG_` section).
     pub comment: String,
     /// Value-to-text mapping (value table).
     pub value_table: BTreeMap<i32, String>,
+    /// Signal type from `SIG_TYPE_REF_`, whose value table applies when `value_table` is empty.
+    pub signal_type: Option<String>,
     // Precomputed extraction steps for fast decoding.
     pub(crate) steps: Vec<Step>,
     /// Multiplexing role (`MuxRole::None` when unused).
@@ -182,6 +189,42 @@ impl CanSignal {
         }
     }
 
+    /// Returns the `(byte_index, mask)` pairs covered by the current
+    /// `bit_start`/`bit_length`/`endian` layout, regardless of the compiled steps.
+    pub(crate) fn byte_masks(&self) -> Vec<(u8, u8)> {
+        let mut probe: CanSignal = CanSignal {
+            bit_start: self.bit_start,
+            bit_length: self.bit_length,
+            endian: self.endian.clone(),
+            ..Default::default()
+        };
+        probe.compile_inline();
+        probe
+            .steps
+            .iter()
+            .map(|st| {
+                (
+                    st.byte_index,
+                    (((1u16 << st.width) - 1) << st.src_lsb) as u8,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the bits used by the signal within a frame of `dlc` bytes as a 64-bit mask
+    /// (LSB = byte 0 bit 0), honoring endianness.
+    ///
+    /// Only the first 8 bytes are representable; bits beyond `dlc` are dropped.
+    pub fn occupied_mask(&self, dlc: u16) -> u64 {
+        let bytes: u16 = dlc.min(8);
+        self.byte_masks()
+            .iter()
+            .filter(|&&(byte, _)| (byte as u16) < bytes)
+            .fold(0u64, |acc, &(byte, mask)| {
+                acc | (mask as u64) << (byte as u32 * 8)
+            })
+    }
+
     #[inline]
     fn push_step(&mut self, st: Step) {
         self.steps.push(st);
@@ -213,11 +256,15 @@ impl CanSignal {
     }
 
     /// Step compilation for big-endian (Motorola) signals.
+    ///
+    /// In DBC, @0: `bit_start` is the MSB of the signal, numbered like Intel bits
+    /// (bit `n` is bit `n % 8` of byte `n / 8`). The field walks down to bit 0 of that
+    /// byte, then continues from bit 7 of the next byte. E.g. a 13-bit signal at
+    /// start bit 7 is `byte0[7..0] ++ byte1[7..3]`.
     fn compile_motorola(&mut self) {
-        // In DBC, @0: the start bit is the MSB of the signal; we advance MSB-first.
         let mut remaining: u16 = self.bit_length;
         let mut byte: usize = (self.bit_start / 8) as usize;
-        let mut bit_msb: u8 = 7 - (self.bit_start % 8) as u8;
+        let mut bit_msb: u8 = (self.bit_start % 8) as u8;
 
         while remaining > 0 {
             let can_take: u16 = (bit_msb as u16 + 1).min(remaining);
@@ -242,6 +289,9 @@ impl CanSignal {
     }
 
     /// Extracts the **unsigned** raw value (LSB-first accumulation) from the payload.
+    ///
+    /// Only the lowest 64 bits are returned: signals with `bit_length > 64` are truncated,
+    /// use [`CanSignal::extract_raw_bytes`] for them.
     #[inline]
     pub fn extract_raw_u64(&self, bytes: &[u8]) -> u64 {
         let mut out: u64 = 0;
@@ -267,6 +317,72 @@ impl CanSignal {
         out
     }
 
+    /// Extracts the raw value of a signal of any length as little-endian bytes.
+    ///
+    /// The result holds `ceil(bit_length / 8)` bytes, LSB first; unused high bits of the
+    /// last byte and bits beyond the payload are zero.
+    pub fn extract_raw_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut out: Vec<u8> = vec![0; (self.bit_length as usize).div_ceil(8)];
+        for st in &self.steps {
+            let Some(&b) = bytes.get(st.byte_index as usize) else {
+                continue;
+            };
+            let mask: u8 = ((1u16 << st.width) - 1) as u8;
+            let chunk: u16 = (((b >> st.src_lsb) & mask) as u16) << (st.dst_lsb % 8);
+            let idx: usize = (st.dst_lsb / 8) as usize;
+            if let Some(lo) = out.get_mut(idx) {
+                *lo |= chunk as u8;
+            }
+            if let Some(hi) = out.get_mut(idx + 1) {
+                *hi |= (chunk >> 8) as u8;
+            }
+        }
+        out
+    }
+
+    /// Writes `raw` into the signal bits of the payload, leaving the other bits untouched.
+    ///
+    /// Inverse of [`CanSignal::extract_raw_u64`]; bits beyond the payload length are dropped.
+    pub fn encode_raw_u64(&self, bytes: &mut [u8], raw: u64) {
+        for st in &self.steps {
+            if st.dst_lsb >= 64 {
+                continue;
+            }
+            if let Some(b) = bytes.get_mut(st.byte_index as usize) {
+                let mask: u8 = (((1u16 << st.width) - 1) << st.src_lsb) as u8;
+                let chunk: u8 = ((raw >> st.dst_lsb) as u8) << st.src_lsb;
+                *b = (*b & !mask) | (chunk & mask);
+            }
+        }
+    }
+
+    /// Writes the physical value `phys` into the signal bits of the payload.
+    ///
+    /// The value is clamped to [`CanSignal::effective_range`], converted to raw
+    /// (`(phys - offset) / factor`, rounded), and stored as two's complement for signed
+    /// signals or as IEEE bits for float/double signals.
+    pub fn encode_physical(&self, bytes: &mut [u8], phys: f64) {
+        let (min, max) = self.effective_range();
+        let phys: f64 = phys.clamp(min, max);
+        let raw_f: f64 = if self.factor != 0.0 {
+            (phys - self.offset) / self.factor
+        } else {
+            0.0
+        };
+        let raw: u64 = match self.sign {
+            Signess::IeeeFloat => (raw_f as f32).to_bits() as u64,
+            Signess::IeeeDouble => raw_f.to_bits(),
+            Signess::Signed => raw_f.round() as i64 as u64,
+            Signess::Unsigned => raw_f.round().max(0.0) as u64,
+        };
+        let mask: u64 = if self.bit_length >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.bit_length) - 1
+        };
+        self.encode_raw_u64(bytes, raw & mask);
+    }
+
     /// Extracts the **signed** raw value from the payload, performing sign extension if needed.
     #[inline]
     pub fn extract_raw_i64(&self, bytes: &[u8]) -> i64 {
@@ -285,6 +401,93 @@ impl CanSignal {
         }
     }
 
+    /// Extracts the raw value from the payload and converts it to its physical value
+    /// (`raw * factor + offset`), honoring the signal sign and IEEE float/double encodings.
+    #[inline]
+    pub fn extract_physical(&self, bytes: &[u8]) -> f64 {
+        let raw: f64 = match self.sign {
+            Signess::Signed => self.extract_raw_i64(bytes) as f64,
+            Signess::IeeeFloat | Signess::IeeeDouble => {
+                self.extract_float(bytes).unwrap_or(f64::NAN)
+            }
+            Signess::Unsigned => self.extract_raw_u64(bytes) as f64,
+        };
+        raw * self.factor + self.offset
+    }
+
+    /// Reads the raw bits at the signal position (honoring endianness) and reinterprets them
+    /// as `f32` / `f64` for IEEE float / double signals; `None` for integer signals.
+    pub fn extract_float(&self, bytes: &[u8]) -> Option<f64> {
+        let raw: u64 = self.extract_raw_u64(bytes);
+        match self.sign {
+            Signess::IeeeFloat => Some(f32::from_bits(raw as u32) as f64),
+            Signess::IeeeDouble => Some(f64::from_bits(raw)),
+            Signess::Unsigned | Signess::Signed => None,
+        }
+    }
+
+    /// Returns the value-table description associated with `raw`, if any.
+    pub fn label_for_raw(&self, raw: i64) -> Option<&str> {
+        let entry: i32 = i32::try_from(raw).ok()?;
+        self.value_table.get(&entry).map(String::as_str)
+    }
+
+    /// Returns the raw value whose value-table description is `label` (case-insensitive).
+    pub fn raw_for_label(&self, label: &str) -> Option<i64> {
+        self.value_table
+            .iter()
+            .find(|(_, desc)| desc.eq_ignore_ascii_case(label))
+            .map(|(&raw, _)| raw as i64)
+    }
+
+    /// Returns the `(min, max)` raw values representable with `bit_length` and `sign`
+    /// (the `f32`/`f64` limits for IEEE float/double signals).
+    pub fn raw_range(&self) -> (f64, f64) {
+        let n: i32 = self.bit_length.min(64) as i32;
+        match self.sign {
+            Signess::Unsigned => (0.0, 2f64.powi(n) - 1.0),
+            Signess::Signed if n > 0 => (-(2f64.powi(n - 1)), 2f64.powi(n - 1) - 1.0),
+            Signess::Signed => (0.0, 0.0),
+            Signess::IeeeFloat => (f32::MIN as f64, f32::MAX as f64),
+            Signess::IeeeDouble => (f64::MIN, f64::MAX),
+        }
+    }
+
+    /// Returns the declared `[min, max]`, or the full physical range reachable from
+    /// [`CanSignal::raw_range`] when the declaration is degenerate (e.g. the `[0|0]` shorthand).
+    pub fn effective_range(&self) -> (f64, f64) {
+        if self.min < self.max {
+            return (self.min, self.max);
+        }
+        let (raw_min, raw_max) = self.raw_range();
+        let a: f64 = raw_min * self.factor + self.offset;
+        let b: f64 = raw_max * self.factor + self.offset;
+        (a.min(b), a.max(b))
+    }
+
+    /// Renders the physical value `phys` for display: the value-table label when the
+    /// corresponding raw value has one, otherwise the number followed by the unit.
+    ///
+    /// `precision` sets the number of decimals; `None` drops redundant trailing zeros.
+    pub fn format_value(&self, phys: f64, precision: Option<usize>) -> String {
+        if self.factor != 0.0 {
+            let raw: f64 = ((phys - self.offset) / self.factor).round();
+            if let Some(label) = self.label_for_raw(raw as i64) {
+                return label.to_string();
+            }
+        }
+
+        let number: String = match precision {
+            Some(decimals) => format!("{phys:.decimals$}"),
+            None => format_f64(phys),
+        };
+        if self.unit_of_measurement.is_empty() {
+            number
+        } else {
+            format!("{number} {}", self.unit_of_measurement)
+        }
+    }
+
     // Note: signal-to-frame conversion is implemented in `asc::core::signal_conversion`.
 
     /// Resets all fields to their default values.
@@ -293,6 +496,16 @@ impl CanSignal {
     }
 }
 
+/// Signal type declared by `SGTYPE_`, as far as value tables are concerned.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct SignalType {
+    /// Global value table (`VAL_TABLE_`) named by the `SGTYPE_` statement, if any. Resolved
+    /// when queried, so the table may be declared anywhere in the file.
+    pub value_table_name: Option<String>,
+    /// Value descriptions from `SGTYPE_VAL_`; they take precedence over `value_table_name`.
+    pub value_table: BTreeMap<i32, String>,
+}
+
 /// Byte order used to interpret signal bits inside a CAN frame.
 #[derive(Default, Clone, PartialEq, Debug)]
 pub enum Endianness {
@@ -330,3 +543,91 @@ impl fmt::Display for Signess {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::message_layout::check_signal_fits;
+    use crate::types::errors::MessageLayoutError;
+
+    /// Builds a compiled unsigned Motorola signal.
+    fn motorola(bit_start: u16, bit_length: u16) -> CanSignal {
+        let mut signal: CanSignal = CanSignal {
+            bit_start,
+            bit_length,
+            endian: Endianness::Motorola,
+            ..Default::default()
+        };
+        signal.compile_inline();
+        signal
+    }
+
+    /// Encodes `raw` into an 8-byte payload, checks the expected bytes and that
+    /// extraction reads the same value back.
+    fn assert_layout(signal: &CanSignal, raw: u64, expected: [u8; 8]) {
+        let mut payload: [u8; 8] = [0; 8];
+        signal.encode_raw_u64(&mut payload, raw);
+        assert_eq!(payload, expected, "start bit {}", signal.bit_start);
+        assert_eq!(signal.extract_raw_u64(&expected), raw);
+    }
+
+    #[test]
+    fn motorola_start_bit_is_the_msb() {
+        // 13 bits at 7: byte0[7..0] ++ byte1[7..3]; 0x1ABC = 0xD5 << 5 | 0x1C
+        assert_layout(&motorola(7, 13), 0x1ABC, [0xD5, 0xE0, 0, 0, 0, 0, 0, 0]);
+        // 12 bits at 3: byte0[3..0] ++ byte1[7..0]
+        assert_layout(&motorola(3, 12), 0xABC, [0x0A, 0xBC, 0, 0, 0, 0, 0, 0]);
+        // 16 bits at 23: byte2[7..0] ++ byte3[7..0]
+        assert_layout(&motorola(23, 16), 0x1234, [0, 0, 0x12, 0x34, 0, 0, 0, 0]);
+        // 4 bits at 13: byte1[5..2]
+        assert_layout(&motorola(13, 4), 0b1011, [0, 0x2C, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn motorola_encode_keeps_neighbouring_bits() {
+        let signal: CanSignal = motorola(7, 13);
+        let mut payload: [u8; 8] = [0xFF; 8];
+        signal.encode_raw_u64(&mut payload, 0);
+        assert_eq!(payload, [0x00, 0x07, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn motorola_fit_check_follows_the_msb_convention() {
+        assert!(check_signal_fits(2, 7, 13, Endianness::Motorola).is_ok());
+        assert!(check_signal_fits(2, 3, 12, Endianness::Motorola).is_ok());
+        assert!(check_signal_fits(1, 7, 8, Endianness::Motorola).is_ok());
+        assert!(matches!(
+            check_signal_fits(2, 15, 13, Endianness::Motorola),
+            Err(MessageLayoutError::MotorolaEndOutOfBounds {
+                end: 20,
+                total_bits: 16,
+                dlc: 2,
+            })
+        ));
+        assert!(matches!(
+            check_signal_fits(1, 3, 12, Endianness::Motorola),
+            Err(MessageLayoutError::MotorolaEndOutOfBounds { end: 15, .. })
+        ));
+    }
+
+    #[test]
+    fn format_value_prefers_labels_then_precision_and_unit() {
+        let gear: CanSignal = CanSignal {
+            factor: 1.0,
+            value_table: BTreeMap::from([(0, "Park".to_string()), (2, "Drive".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(gear.format_value(2.0, Some(2)), "Drive");
+        assert_eq!(gear.format_value(1.0, None), "1");
+
+        let temp: CanSignal = CanSignal {
+            factor: 0.1,
+            offset: -40.0,
+            unit_of_measurement: "degC".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(temp.format_value(21.456, Some(2)), "21.46 degC");
+        assert_eq!(temp.format_value(21.5, None), "21.5 degC");
+        assert_eq!(temp.format_value(21.0, Some(0)), "21 degC");
+    }
+}
@@ -0,0 +1,197 @@
+//! ARXML import from the fixtures in `tests/fixtures`.
+
+use can_tools::{
+    parse::{from_arxml_file, from_arxml_file_with_progress, from_reader},
+    save::to_dbc_string,
+    types::{
+        attributes::AttributeValue,
+        database::{BusType, CanDatabase},
+        message::{FrameFormat, IdFormat, MuxSelector},
+    },
+};
+
+/// Loads `tests/fixtures/<name>`, which must hold a single `CAN-CLUSTER`.
+fn load(name: &str) -> CanDatabase {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    let mut databases = from_arxml_file(&path).expect("fixture should load");
+    assert_eq!(databases.len(), 1, "one cluster per fixture");
+    databases.remove(0)
+}
+
+#[test]
+fn compu_method_scaling_unit_and_limits_are_imported() {
+    let db = load("scaled_signal.arxml");
+    assert_eq!(db.name, "Powertrain");
+
+    // network representation: LINEAR compu-method, unit display name, physical limits
+    let rpm = db.get_signal_by_name("Rpm").unwrap();
+    assert_eq!((rpm.bit_start, rpm.bit_length), (0, 16));
+    assert_eq!((rpm.factor, rpm.offset), (0.25, -500.0));
+    assert_eq!(rpm.unit_of_measurement, "1/min");
+    assert_eq!((rpm.min, rpm.max), (-500.0, 8000.0));
+
+    // system signal physical props: denominator applied, internal limits scaled
+    let coolant = db.get_signal_by_name("Coolant").unwrap();
+    assert_eq!((coolant.factor, coolant.offset), (0.5, -40.0));
+    assert_eq!(coolant.unit_of_measurement, "degC");
+    assert_eq!((coolant.min, coolant.max), (-40.0, 85.0));
+
+    let msg = db.get_msg_key_by_name("EngineData").unwrap();
+    let rpm_key = db.get_sig_key_by_name("Rpm").unwrap();
+    let payload = [0xA0, 0x0F, 0, 0, 0, 0, 0, 0];
+    let (value, _) = db.decode_signal_labeled(msg, rpm_key, &payload).unwrap();
+    assert_eq!(value, 4000.0 * 0.25 - 500.0);
+}
+
+#[test]
+fn texttable_compu_scales_become_the_value_table() {
+    let db = load("value_table.arxml");
+    let gear = db.get_signal_by_name("Gear").unwrap();
+    let labels: Vec<(i32, &str)> = gear
+        .value_table
+        .iter()
+        .map(|(&raw, label)| (raw, label.as_str()))
+        .collect();
+    assert_eq!(labels, [(0, "Park"), (1, "Reverse"), (2, "Drive")]);
+    // a TEXTTABLE carries no linear scaling
+    assert_eq!((gear.factor, gear.offset), (1.0, 0.0));
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(
+        text.lines()
+            .any(|l| l == "VAL_ 100 Gear 0 \"Park\" 1 \"Reverse\" 2 \"Drive\" ;"),
+        "{text}"
+    );
+    let reparsed = from_reader(text.as_bytes()).unwrap();
+    assert_eq!(
+        reparsed.get_signal_by_name("Gear").unwrap().value_table,
+        gear.value_table
+    );
+}
+
+#[test]
+fn frame_format_is_set_per_frame_triggering() {
+    let db = load("frame_formats.arxml");
+    let format_of = |name: &str| {
+        let msg = db.get_message_by_name(name).unwrap();
+        (msg.byte_length, msg.frame_format)
+    };
+
+    assert_eq!(format_of("Classic"), (8, Some(FrameFormat::StandardCan)));
+    // more than 8 bytes can only travel as CAN FD
+    assert_eq!(
+        format_of("FdByLength"),
+        (64, Some(FrameFormat::ExtendedCanFd))
+    );
+    // an 8-byte frame is FD only when the triggering says so
+    assert_eq!(
+        format_of("FdByBehavior"),
+        (8, Some(FrameFormat::StandardCanFd))
+    );
+    assert_eq!(
+        db.get_message_by_name("FdByLength").unwrap().id_format,
+        IdFormat::Extended
+    );
+}
+
+#[test]
+fn multiplexed_ipdu_selector_drives_the_dynamic_parts() {
+    let db = load("multiplexed_ipdu.arxml");
+    let msg_key = db.get_msg_key_by_name("Telemetry").unwrap();
+    let msg = db.get_message_by_key(msg_key).unwrap();
+
+    let selector = db.get_sig_key_by_name("Telemetry_Mux_Selector").unwrap();
+    assert_eq!(msg.mux_multiplexors, [selector]);
+    let selector_signal = db.get_sig_by_key(selector).unwrap();
+    assert_eq!(
+        (selector_signal.bit_start, selector_signal.bit_length),
+        (0, 8)
+    );
+    let mut codes: Vec<&MuxSelector> = msg.mux_cases[&selector].keys().collect();
+    codes.sort_by_key(|code| format!("{code:?}"));
+    assert_eq!(codes, [&MuxSelector::Value(1), &MuxSelector::Value(2)]);
+
+    let active = |payload: &[u8]| {
+        let frame = db.interpret_frame(768, payload).unwrap();
+        assert!(!frame.unknown_mux);
+        frame
+            .signals
+            .iter()
+            .map(|s| (s.name.clone(), s.raw))
+            .collect::<Vec<_>>()
+    };
+    let mut temp = active(&[0x01, 0x07, 0x34, 0x12, 0, 0, 0, 0]);
+    temp.sort();
+    assert_eq!(
+        temp,
+        [
+            ("Status".to_string(), 0x07),
+            ("Telemetry_Mux_Selector".to_string(), 1),
+            ("Temp".to_string(), 0x1234),
+        ]
+    );
+    let mut volt = active(&[0x02, 0x07, 0x34, 0x12, 0, 0, 0, 0]);
+    volt.sort();
+    assert_eq!(
+        volt,
+        [
+            ("Status".to_string(), 0x07),
+            ("Telemetry_Mux_Selector".to_string(), 2),
+            ("Voltage".to_string(), 0x1234),
+        ]
+    );
+}
+
+#[test]
+fn cluster_baud_rates_become_database_attributes() {
+    let db = load("fd_baudrate.arxml");
+    assert_eq!(db.bustype, BusType::CanFd);
+    assert_eq!(
+        db.attributes.get("Baudrate"),
+        Some(&AttributeValue::Int(500_000))
+    );
+    assert_eq!(
+        db.attributes.get("BaudrateCANFD"),
+        Some(&AttributeValue::Int(2_000_000))
+    );
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.contains("BA_ \"BaudrateCANFD\" 2000000;"), "{text}");
+
+    // without CAN-FD-BAUDRATE the cluster stays classic CAN
+    let classic = load("value_table.arxml");
+    assert_eq!(classic.bustype, BusType::Can);
+    assert_eq!(
+        classic.attributes.get("Baudrate"),
+        Some(&AttributeValue::Int(500_000))
+    );
+    assert!(!classic.attributes.contains_key("BaudrateCANFD"));
+}
+
+#[test]
+fn ecu_instance_description_becomes_the_node_comment() {
+    let db = load("ecu_description.arxml");
+    let engine = db.get_node_by_name("Engine").unwrap();
+    assert_eq!(engine.comment, "Engine control unit");
+    // an ECU without DESC leaves the comment empty
+    assert_eq!(db.get_node_by_name("Dash").unwrap().comment, "");
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(
+        text.contains("CM_ BU_ Engine \"Engine control unit\";"),
+        "{text}"
+    );
+    assert!(!text.contains("CM_ BU_ Dash"), "{text}");
+}
+
+#[test]
+fn arxml_progress_counts_converted_clusters() {
+    let path = format!(
+        "{}/tests/fixtures/scaled_signal.arxml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let mut calls: Vec<(u64, u64)> = Vec::new();
+    let databases =
+        from_arxml_file_with_progress(&path, |done, total| calls.push((done, total))).unwrap();
+    assert_eq!(databases.len(), 1);
+    assert_eq!(calls, [(1, 1)]);
+}
@@ -0,0 +1,8 @@
+//! Helpers shared by the integration tests.
+
+use can_tools::{parse::from_reader, types::database::CanDatabase};
+
+/// Parses an in-memory DBC fixture, panicking on read errors.
+pub fn parse(dbc: &str) -> CanDatabase {
+    from_reader(dbc.as_bytes()).expect("fixture should parse")
+}
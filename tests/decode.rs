@@ -0,0 +1,387 @@
+//! Frame decoding and encoding against hand-computed payloads.
+
+mod common;
+
+use can_tools::{
+    DatabaseError,
+    types::{database::CanDatabase, frame::ChecksumAlgo, signal::Endianness},
+};
+use common::parse;
+use std::collections::HashMap;
+
+const FIXTURE: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Gearbox Dash
+
+BO_ 100 GearStatus: 8 Gearbox
+ SG_ Gear : 0|4@1+ (1,0) [0|15] "" Dash
+ SG_ Speed : 8|16@1+ (0.1,0) [0|6553.5] "km/h" Dash
+
+BO_ 101 Packed: 3 Gearbox
+ SG_ Wide : 0|12@1+ (1,0) [0|4095] "" Dash
+ SG_ Nibble : 12|4@1+ (0.5,0) [0|7.5] "" Dash
+
+BO_ 102 Muxed: 2 Gearbox
+ SG_ Mode M : 0|4@1+ (1,0) [0|15] "" Dash
+ SG_ Temp m0 : 8|8@1- (1,-40) [-168|87] "degC" Dash
+ SG_ Pressure m1 : 8|8@1+ (10,0) [0|2550] "hPa" Dash
+
+BO_ 103 Protected: 4 Gearbox
+ SG_ Data : 0|8@1+ (1,0) [0|255] "" Dash
+ SG_ Counter : 8|4@1+ (1,0) [0|15] "" Dash
+ SG_ Checksum : 24|8@1+ (1,0) [0|255] "" Dash
+
+VAL_ 100 Gear 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+"#;
+
+#[test]
+fn decode_signal_labeled_returns_value_and_label() {
+    let db = parse(FIXTURE);
+    let msg = db.get_msg_key_by_name("GearStatus").unwrap();
+    let gear = db.get_sig_key_by_name("Gear").unwrap();
+    let speed = db.get_sig_key_by_name("Speed").unwrap();
+    let payload = [0x03, 0xE8, 0x03, 0, 0, 0, 0, 0];
+
+    let (value, label) = db.decode_signal_labeled(msg, gear, &payload).unwrap();
+    assert_eq!(value, 3.0);
+    assert_eq!(label.as_deref(), Some("Drive"));
+
+    let (value, label) = db.decode_signal_labeled(msg, speed, &payload).unwrap();
+    assert!((value - 100.0).abs() < 1e-9);
+    assert_eq!(label, None);
+}
+
+#[test]
+fn message_annotation_maps_bytes_to_signals() {
+    let db = parse(FIXTURE);
+    let msg = db.get_msg_key_by_name("Packed").unwrap();
+    let annotations = db.message_annotation(msg, &[0x34, 0xA2, 0x7F]);
+    assert_eq!(annotations.len(), 3);
+
+    let slices = |i: usize| -> Vec<(&str, u8, u8, f64)> {
+        annotations[i]
+            .signals
+            .iter()
+            .map(|s| (s.name.as_str(), s.mask, s.bits, s.physical))
+            .collect()
+    };
+    assert_eq!(slices(0), [("Wide", 0xFF, 0x34, 564.0)]);
+    assert_eq!(
+        slices(1),
+        [("Nibble", 0xF0, 0xA0, 5.0), ("Wide", 0x0F, 0x02, 564.0)]
+    );
+    assert!(slices(2).is_empty());
+    assert_eq!(annotations[1].value, 0xA2);
+}
+
+#[test]
+fn message_annotation_skips_unselected_multiplexed_signals() {
+    let db = parse(FIXTURE);
+    let msg = db.get_msg_key_by_name("Muxed").unwrap();
+    let annotations = db.message_annotation(msg, &[0x01, 0x05]);
+    let names: Vec<&str> = annotations[1]
+        .signals
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, ["Pressure"]);
+    assert_eq!(annotations[1].signals[0].physical, 50.0);
+}
+
+#[test]
+fn interpret_frame_decodes_a_known_frame() {
+    let db = parse(FIXTURE);
+    let frame = db
+        .interpret_frame(100, &[0x01, 0x10, 0x27, 0, 0, 0, 0, 0])
+        .unwrap();
+    assert_eq!(frame.message_name, "GearStatus");
+    assert!(!frame.unknown_mux);
+
+    let decoded: Vec<(&str, i64, f64, Option<&str>, &str)> = frame
+        .signals
+        .iter()
+        .map(|s| {
+            (
+                s.name.as_str(),
+                s.raw,
+                s.value,
+                s.label.as_deref(),
+                s.unit.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        decoded,
+        [
+            ("Gear", 1, 1.0, Some("Reverse"), ""),
+            ("Speed", 10000, 1000.0, None, "km/h"),
+        ]
+    );
+    assert!(db.interpret_frame(999, &[0; 8]).is_none());
+}
+
+#[test]
+fn interpret_frame_honors_multiplexing() {
+    let db = parse(FIXTURE);
+    let frame = db.interpret_frame(102, &[0x00, 0x3C]).unwrap();
+    let active: Vec<(&str, f64)> = frame
+        .signals
+        .iter()
+        .map(|s| (s.name.as_str(), s.value))
+        .collect();
+    assert_eq!(active, [("Mode", 0.0), ("Temp", 20.0)]);
+    assert!(!frame.unknown_mux);
+
+    let frame = db.interpret_frame(102, &[0x05, 0x3C]).unwrap();
+    assert_eq!(frame.signals.len(), 1);
+    assert!(frame.unknown_mux);
+}
+
+#[test]
+fn format_frame_prints_labels_units_and_raw_values() {
+    let db = parse(FIXTURE);
+    let status = db.get_msg_key_by_name("GearStatus").unwrap();
+    let text = db.format_frame(status, &[0x03, 0xE8, 0x03, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        text,
+        "GearStatus (0x064) [03 E8 03 00 00 00 00 00]\n  Gear = Drive (raw=0x3)\n  Speed = 100 km/h (raw=0x3E8)\n"
+    );
+}
+
+#[test]
+fn format_frame_shows_only_active_multiplexed_signals() {
+    let db = parse(FIXTURE);
+    let muxed = db.get_msg_key_by_name("Muxed").unwrap();
+    let text = db.format_frame(muxed, &[0x00, 0xF6]);
+    assert!(text.contains("  Temp = -50 degC (raw=0xF6)\n"), "{text}");
+    assert!(!text.contains("Pressure"), "{text}");
+}
+
+#[test]
+fn finalize_frame_wraps_the_counter_and_writes_the_checksum() {
+    let mut db = parse(FIXTURE);
+    let msg = db.get_msg_key_by_name("Protected").unwrap();
+    let counter = db.get_sig_key_by_name("Counter").unwrap();
+    let checksum = db.get_sig_key_by_name("Checksum").unwrap();
+    db.set_counter_signal(msg, counter).unwrap();
+    db.set_checksum_signal(msg, checksum).unwrap();
+
+    let mut buf = [0x12, 0x0E, 0x00, 0x00];
+    db.finalize_frame(msg, &mut buf, ChecksumAlgo::Xor).unwrap();
+    // counter 14 -> 15; XOR over bytes 0..3, the checksum byte excluded
+    assert_eq!(buf, [0x12, 0x0F, 0x00, 0x12 ^ 0x0F]);
+
+    db.finalize_frame(msg, &mut buf, ChecksumAlgo::Xor).unwrap();
+    // counter 15 wraps to 0 at its 4-bit width
+    assert_eq!(buf, [0x12, 0x00, 0x00, 0x12]);
+
+    // the same input always yields the same checksum
+    let mut first = [0x12, 0x0E, 0x00, 0x00];
+    let mut second = first;
+    db.finalize_frame(msg, &mut first, ChecksumAlgo::Sum8)
+        .unwrap();
+    db.finalize_frame(msg, &mut second, ChecksumAlgo::Sum8)
+        .unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first[3], 0x12 + 0x0F);
+}
+
+#[test]
+fn set_checksum_signal_requires_a_signal_of_the_message() {
+    let mut db = parse(FIXTURE);
+    let msg = db.get_msg_key_by_name("Protected").unwrap();
+    let gear = db.get_sig_key_by_name("Gear").unwrap();
+
+    assert!(db.set_checksum_signal(msg, gear).is_err());
+    assert_eq!(db.get_message_by_key(msg).unwrap().checksum_signal, None);
+}
+
+#[test]
+fn degenerate_range_falls_back_to_the_representable_range() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Raw: 2 Ecu\n\
+         \x20SG_ Level : 0|12@1+ (0.5,10) [0|0] \"\" Ecu\n",
+    );
+    let level = db.get_signal_by_name("Level").unwrap();
+    assert_eq!(level.raw_range(), (0.0, 4095.0));
+    assert_eq!(level.effective_range(), (10.0, 4095.0 * 0.5 + 10.0));
+
+    // encoding clamps to the effective range instead of the declared [0|0]
+    let mut payload = [0u8; 2];
+    level.encode_physical(&mut payload, 500.0);
+    assert_eq!(level.extract_raw_u64(&payload), 980);
+    level.encode_physical(&mut payload, 1e6);
+    assert_eq!(level.extract_raw_u64(&payload), 4095);
+}
+
+#[test]
+fn extract_float_reinterprets_ieee_bits_in_either_byte_order() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Floats: 8 Ecu\n\
+         \x20SG_ Little : 0|32@1- (1,0) [0|0] \"\" Ecu\n\
+         \x20SG_ Big : 39|32@0- (1,0) [0|0] \"\" Ecu\n\n\
+         BO_ 2 Doubles: 8 Ecu\n\
+         \x20SG_ Wide : 0|64@1- (1,0) [0|0] \"\" Ecu\n\n\
+         BO_ 3 Integers: 1 Ecu\n\
+         \x20SG_ Plain : 0|8@1+ (1,0) [0|255] \"\" Ecu\n\n\
+         SIG_VALTYPE_ 1 Little : 1;\n\
+         SIG_VALTYPE_ 1 Big : 1;\n\
+         SIG_VALTYPE_ 2 Wide : 2;\n",
+    );
+    // 1.5f32 = 0x3FC00000, -2.25f32 = 0xC0100000
+    let payload = [0x00, 0x00, 0xC0, 0x3F, 0xC0, 0x10, 0x00, 0x00];
+    let little = db.get_signal_by_name("Little").unwrap();
+    assert_eq!(little.extract_float(&payload), Some(1.5));
+    let big = db.get_signal_by_name("Big").unwrap();
+    assert_eq!(big.extract_float(&payload), Some(-2.25));
+
+    let wide = db.get_signal_by_name("Wide").unwrap();
+    assert_eq!(
+        wide.extract_float(&std::f64::consts::PI.to_le_bytes()),
+        Some(std::f64::consts::PI)
+    );
+    assert_eq!(
+        db.get_signal_by_name("Plain")
+            .unwrap()
+            .extract_float(&payload),
+        None
+    );
+}
+
+#[test]
+fn build_frame_encodes_named_values_over_start_values() {
+    let db = parse(&format!(
+        "{FIXTURE}\nBA_DEF_ SG_ \"GenSigStartValue\" INT 0 65535;\n\
+         BA_DEF_DEF_ \"GenSigStartValue\" 0;\n\
+         BA_ \"GenSigStartValue\" SG_ 100 Gear 2;\n"
+    ));
+    let msg = db.get_msg_key_by_name("GearStatus").unwrap();
+    let gear = db.get_sig_key_by_name("Gear").unwrap();
+    let speed = db.get_sig_key_by_name("Speed").unwrap();
+
+    let values = HashMap::from([("Gear".to_string(), 3.0), ("Speed".to_string(), 120.5)]);
+    let payload = db.build_frame(msg, &values).unwrap();
+    assert_eq!(payload, [0x03, 0xB5, 0x04, 0, 0, 0, 0, 0]);
+    let (value, label) = db.decode_signal_labeled(msg, gear, &payload).unwrap();
+    assert_eq!((value, label.as_deref()), (3.0, Some("Drive")));
+    let (value, _) = db.decode_signal_labeled(msg, speed, &payload).unwrap();
+    assert!((value - 120.5).abs() < 1e-9);
+
+    // an omitted signal keeps its start value
+    let payload = db
+        .build_frame(msg, &HashMap::from([("Speed".to_string(), 0.0)]))
+        .unwrap();
+    assert_eq!(payload, [0x02, 0, 0, 0, 0, 0, 0, 0]);
+
+    assert!(matches!(
+        db.build_frame(msg, &HashMap::from([("Rpm".to_string(), 1.0)])),
+        Err(DatabaseError::SignalNameNotFound { name }) if name == "Rpm"
+    ));
+}
+
+#[test]
+fn extract_raw_bytes_reads_signals_longer_than_64_bits() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Bulk: 64 Ecu\n\
+         \x20SG_ Blob : 12|96@1+ (1,0) [0|0] \"\" Ecu\n",
+    );
+    let blob = db.get_signal_by_name("Blob").unwrap();
+    assert_eq!(db.get_message_by_name("Bulk").unwrap().signals.len(), 1);
+
+    let value: u128 = 0x0123_4567_89AB_CDEF_FEDC_BA98;
+    let mut payload = [0xFFu8; 64];
+    payload[..16].copy_from_slice(&((value << 12) | 0xFFF).to_le_bytes());
+    // neighbouring bits are all set: bits 0..12 and 108..
+    payload[13] |= 0xF0;
+
+    assert_eq!(blob.extract_raw_bytes(&payload), value.to_le_bytes()[..12]);
+    // the u64 path keeps only the low 64 bits
+    assert_eq!(blob.extract_raw_u64(&payload), value as u64);
+}
+
+#[test]
+fn convert_message_endianness_keeps_values_with_mirrored_bytes() {
+    let mut db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Frame: 4 Ecu\n\
+         \x20SG_ Word : 7|16@0+ (0.1,0) [0|6553.5] \"\" Ecu\n\
+         \x20SG_ Odd : 19|12@0+ (1,0) [0|4095] \"\" Ecu\n",
+    );
+    let msg = db.get_msg_key_by_name("Frame").unwrap();
+    let word = db.get_sig_key_by_name("Word").unwrap();
+    let odd = db.get_sig_key_by_name("Odd").unwrap();
+    let decode = |db: &CanDatabase, payload: &[u8]| {
+        (
+            db.decode_signal_labeled(msg, word, payload).unwrap().0,
+            db.get_sig_by_key(odd).unwrap().extract_raw_u64(payload),
+        )
+    };
+
+    let motorola_payload = [0x12, 0x34, 0x0A, 0xBC];
+    let before = decode(&db, &motorola_payload);
+    assert_eq!(before.1, 0xABC);
+
+    db.convert_message_endianness(msg, Endianness::Intel)
+        .unwrap();
+    let layout = |db: &CanDatabase, key| {
+        let s = db.get_sig_by_key(key).unwrap();
+        (s.bit_start, s.bit_length, s.endian.clone())
+    };
+    assert_eq!(layout(&db, word), (0, 16, Endianness::Intel));
+    assert_eq!(layout(&db, odd), (16, 12, Endianness::Intel));
+    // each signal's bytes reversed within its span
+    let intel_payload = [0x34, 0x12, 0xBC, 0x0A];
+    assert_eq!(decode(&db, &intel_payload), before);
+
+    db.convert_message_endianness(msg, Endianness::Motorola)
+        .unwrap();
+    assert_eq!(layout(&db, word), (7, 16, Endianness::Motorola));
+    assert_eq!(layout(&db, odd), (19, 12, Endianness::Motorola));
+    assert_eq!(decode(&db, &motorola_payload), before);
+}
+
+#[test]
+fn encode_labeled_writes_the_raw_value_of_a_label() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Command: 2 Ecu\n\
+         \x20SG_ Mode : 4|4@1+ (1,0) [0|15] \"\" Ecu\n\
+         \x20SG_ Trim : 8|8@1- (1,0) [-128|127] \"\" Ecu\n\n\
+         VAL_ 1 Mode 0 \"Auto\" 1 \"Manual\" 2 \"Service\" ;\n\
+         VAL_ 1 Trim -1 \"Down\" 0 \"Hold\" 1 \"Up\" ;\n",
+    );
+    let msg = db.get_msg_key_by_name("Command").unwrap();
+    let mode = db.get_sig_key_by_name("Mode").unwrap();
+    let trim = db.get_sig_key_by_name("Trim").unwrap();
+
+    let mut payload = [0x0F, 0x00];
+    db.encode_labeled(msg, "Mode", "manual", &mut payload)
+        .unwrap();
+    assert_eq!(payload, [0x1F, 0x00]);
+    let (_, label) = db.decode_signal_labeled(msg, mode, &payload).unwrap();
+    assert_eq!(label.as_deref(), Some("Manual"));
+
+    // negative raw values are written in two's complement within the signal width
+    db.encode_labeled(msg, "Trim", "Down", &mut payload)
+        .unwrap();
+    assert_eq!(payload, [0x1F, 0xFF]);
+    let (value, label) = db.decode_signal_labeled(msg, trim, &payload).unwrap();
+    assert_eq!((value, label.as_deref()), (-1.0, Some("Down")));
+
+    assert!(matches!(
+        db.encode_labeled(msg, "Mode", "Turbo", &mut payload),
+        Err(DatabaseError::ValueTableEntryMissing { entry, .. }) if entry == "Turbo"
+    ));
+    assert!(matches!(
+        db.encode_labeled(msg, "Gear", "Auto", &mut payload),
+        Err(DatabaseError::SignalNameNotFound { name }) if name == "Gear"
+    ));
+    assert_eq!(payload, [0x1F, 0xFF]);
+}
@@ -0,0 +1,821 @@
+//! Editing operations on a `CanDatabase`.
+
+mod common;
+
+use can_tools::{
+    DatabaseError,
+    types::{
+        attributes::{AttrObject, AttributeDef, AttributeValue},
+        database::{CanDatabase, CanSignalKey, NamePolicy},
+        message::{MuxRole, MuxSelector},
+        signal::{Endianness, Signess},
+    },
+};
+use common::parse;
+use slotmap::Key;
+
+const CHASSIS: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Chassis Dash
+
+BO_ 1 Source: 8 Chassis
+ SG_ Yaw : 40|16@1- (0.01,0) [-327.68|327.67] "deg/s" Dash
+
+BO_ 2 Short: 4 Chassis
+
+BO_ 3 Target: 8 Chassis
+
+BA_DEF_ BO_ "Priority" INT 0 7;
+BA_DEF_ SG_ "Kind" ENUM "Raw","Filtered";
+BA_DEF_DEF_ "Priority" 0;
+BA_DEF_DEF_ "Kind" "Raw";
+"#;
+
+#[test]
+fn add_message_rejects_illegal_can_fd_lengths() {
+    let mut db = CanDatabase::default();
+    assert!(matches!(
+        db.add_message("M", 1, 9),
+        Err(DatabaseError::InvalidDlc { byte_length: 9 })
+    ));
+    assert!(db.add_message("M", 1, 12).is_ok());
+    assert!(db.add_message("Classic", 2, 5).is_ok());
+}
+
+#[test]
+fn add_message_round_up_opts_into_the_next_legal_length() {
+    let mut db = CanDatabase::default();
+    let key = db.add_message_round_up("M", 1, 9).unwrap();
+    assert_eq!(db.get_message_by_key(key).unwrap().byte_length, 12);
+    assert!(matches!(
+        db.add_message_round_up("Huge", 2, 65),
+        Err(DatabaseError::InvalidDlc { byte_length: 65 })
+    ));
+}
+
+#[test]
+fn c_identifier_policy_accepts_valid_names() {
+    let mut db = CanDatabase::default();
+    db.set_name_policy(NamePolicy::CIdentifier);
+    assert!(db.add_node("_Ecu1").is_ok());
+    assert!(db.add_message("Msg_2", 1, 8).is_ok());
+    assert!(
+        db.add_signal(
+            "speed_kph",
+            Endianness::Intel,
+            Signess::Unsigned,
+            1.0,
+            0.0,
+            0.0,
+            255.0,
+            ""
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn c_identifier_policy_rejects_invalid_names() {
+    let mut db = CanDatabase::default();
+    db.set_name_policy(NamePolicy::CIdentifier);
+    assert!(matches!(
+        db.add_node("1Ecu"),
+        Err(DatabaseError::InvalidName {
+            policy: NamePolicy::CIdentifier,
+            ..
+        })
+    ));
+    assert!(matches!(
+        db.add_message("Bad-Name", 1, 8),
+        Err(DatabaseError::InvalidName { .. })
+    ));
+    assert!(matches!(
+        db.add_signal(
+            "speed kph",
+            Endianness::Intel,
+            Signess::Unsigned,
+            1.0,
+            0.0,
+            0.0,
+            255.0,
+            ""
+        ),
+        Err(DatabaseError::InvalidName { .. })
+    ));
+    assert_eq!(db.node_count() + db.message_count() + db.signal_count(), 0);
+
+    db.set_name_policy(NamePolicy::Lenient);
+    assert!(db.add_node("1Ecu").is_ok());
+}
+
+#[test]
+fn copy_signal_to_message_keeps_the_layout() {
+    let mut db = parse(CHASSIS);
+    let yaw = db.get_sig_key_by_name("Yaw").unwrap();
+    let target = db.get_msg_key_by_name("Target").unwrap();
+
+    let copy = db.copy_signal_to_message(yaw, target).unwrap();
+    let signal = db.get_sig_by_key(copy).unwrap();
+    assert_eq!(signal.name, "Yaw_copy");
+    assert_eq!((signal.bit_start, signal.bit_length), (40, 16));
+    assert_eq!(signal.message, target);
+    assert!(
+        db.get_message_by_key(target)
+            .unwrap()
+            .signals
+            .contains(&copy)
+    );
+}
+
+#[test]
+fn copy_signal_to_message_validates_the_destination_layout() {
+    let mut db = parse(CHASSIS);
+    let yaw = db.get_sig_key_by_name("Yaw").unwrap();
+    let short = db.get_msg_key_by_name("Short").unwrap();
+    let signals_before = db.signal_count();
+
+    assert!(matches!(
+        db.copy_signal_to_message(yaw, short),
+        Err(DatabaseError::Layout(_))
+    ));
+    assert_eq!(db.signal_count(), signals_before);
+    assert!(db.get_signal_by_name("Yaw_copy").is_none());
+}
+
+#[test]
+fn set_message_attribute_rejects_an_out_of_range_int() {
+    let mut db = parse(CHASSIS);
+    let source = db.get_msg_key_by_name("Source").unwrap();
+
+    assert!(matches!(
+        db.set_message_attribute(source, "Priority", AttributeValue::Int(9)),
+        Err(DatabaseError::AttributeTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        db.set_message_attribute(source, "Unknown", AttributeValue::Int(1)),
+        Err(DatabaseError::AttributeNotFound { .. })
+    ));
+    db.set_message_attribute(source, "Priority", AttributeValue::Int(7))
+        .unwrap();
+    assert_eq!(
+        db.remove_message_attribute(source, "Priority").unwrap(),
+        Some(AttributeValue::Int(7))
+    );
+}
+
+#[test]
+fn set_signal_attribute_accepts_a_valid_enum_selection() {
+    let mut db = parse(CHASSIS);
+    let yaw = db.get_sig_key_by_name("Yaw").unwrap();
+
+    db.set_signal_attribute(yaw, "Kind", AttributeValue::Enum("Filtered".into()))
+        .unwrap();
+    assert_eq!(
+        db.get_sig_by_key(yaw).unwrap().attributes["Kind"],
+        AttributeValue::Enum("Filtered".into())
+    );
+    assert!(matches!(
+        db.set_signal_attribute(yaw, "Kind", AttributeValue::Enum("Smoothed".into())),
+        Err(DatabaseError::AttributeTypeMismatch { .. })
+    ));
+    // a signal attribute cannot be set on a message
+    let source = db.get_msg_key_by_name("Source").unwrap();
+    assert!(matches!(
+        db.set_message_attribute(source, "Kind", AttributeValue::Enum("Raw".into())),
+        Err(DatabaseError::AttributeNotFound { .. })
+    ));
+}
+
+#[test]
+fn collect_into_message_binds_every_independent_signal() {
+    let mut db = parse(CHASSIS);
+    for (name, bit_length) in [("Loose", 12), ("Stray", 8)] {
+        let key = db
+            .add_signal(
+                name,
+                Endianness::Intel,
+                Signess::Unsigned,
+                1.0,
+                0.0,
+                0.0,
+                0.0,
+                "",
+            )
+            .unwrap();
+        db.get_sig_by_key_mut(key).unwrap().bit_length = bit_length;
+    }
+
+    let collected = db.collect_into_message("Collected", 0x10).unwrap();
+    let message = db.get_message_by_key(collected).unwrap();
+    assert_eq!(message.byte_length, 3);
+    assert_eq!(message.signals.len(), 2);
+
+    let layout = |name: &str| {
+        let signal = db.get_signal_by_name(name).unwrap();
+        assert_eq!(signal.message, collected);
+        (signal.bit_start, signal.bit_length)
+    };
+    assert_eq!(layout("Loose"), (0, 12));
+    assert_eq!(layout("Stray"), (12, 8));
+
+    assert!(db.signals.values().all(|s| !s.message.is_null()));
+    let text = can_tools::save::to_dbc_string(&db).unwrap();
+    assert!(!text.contains("INDEPENDENT_SIG_MSG"), "{text}");
+}
+
+#[test]
+fn failed_collect_into_message_leaves_the_signals_untouched() {
+    let mut db = parse(CHASSIS);
+    loose_signal(&mut db, "Loose", 40, 8);
+
+    // the name is taken: the layout must not have been rewritten
+    assert!(matches!(
+        db.collect_into_message("Source", 0x10),
+        Err(DatabaseError::MessageAlreadyExists { .. })
+    ));
+    let loose = db.get_signal_by_name("Loose").unwrap();
+    assert_eq!((loose.bit_start, loose.message.is_null()), (40, true));
+
+    // 600 bits cannot fit next to the first signal in 64 bytes
+    loose_signal(&mut db, "Wide", 8, 600);
+    match db.collect_into_message("Collected", 0x10) {
+        Err(DatabaseError::SignalDoesNotFit { name }) => assert_eq!(name, "Wide"),
+        other => panic!("expected SignalDoesNotFit, got {other:?}"),
+    }
+    assert_eq!(db.get_signal_by_name("Loose").unwrap().bit_start, 40);
+    assert_eq!(db.get_signal_by_name("Wide").unwrap().bit_start, 8);
+    assert!(db.get_message_by_name("Collected").is_none());
+}
+
+/// Adds an unsigned Intel signal of `bit_length` bits at `bit_start`, not yet bound.
+fn loose_signal(db: &mut CanDatabase, name: &str, bit_start: u16, bit_length: u16) -> CanSignalKey {
+    let key = db
+        .add_signal(
+            name,
+            Endianness::Intel,
+            Signess::Unsigned,
+            1.0,
+            0.0,
+            0.0,
+            0.0,
+            "",
+        )
+        .unwrap();
+    let signal = db.get_sig_by_key_mut(key).unwrap();
+    signal.bit_start = bit_start;
+    signal.bit_length = bit_length;
+    key
+}
+
+#[test]
+fn add_msg_sig_relation_rejects_overlapping_signals() {
+    let mut db = parse(CHASSIS);
+    let target = db.get_msg_key_by_name("Target").unwrap();
+    let first = loose_signal(&mut db, "First", 0, 8);
+    let second = loose_signal(&mut db, "Second", 0, 8);
+
+    db.add_msg_sig_relation(first, target, MuxRole::None, None)
+        .unwrap();
+    assert!(matches!(
+        db.add_msg_sig_relation(second, target, MuxRole::None, None),
+        Err(DatabaseError::SignalOverlap { existing, incoming })
+            if existing == first && incoming == second
+    ));
+    assert!(db.get_sig_by_key(second).unwrap().message.is_null());
+
+    // importers can still stack the signals on purpose
+    db.add_msg_sig_relation_unchecked(second, target, MuxRole::None, None)
+        .unwrap();
+    assert_eq!(db.get_sig_by_key(second).unwrap().message, target);
+}
+
+#[test]
+fn add_msg_sig_relation_allows_overlap_on_disjoint_mux_selectors() {
+    let mut db = parse(CHASSIS);
+    let target = db.get_msg_key_by_name("Target").unwrap();
+    let switch = loose_signal(&mut db, "Switch", 0, 4);
+    let case_a = loose_signal(&mut db, "CaseA", 8, 8);
+    let case_b = loose_signal(&mut db, "CaseB", 8, 8);
+    let case_c = loose_signal(&mut db, "CaseC", 8, 8);
+
+    db.add_msg_sig_relation(switch, target, MuxRole::Multiplexor, None)
+        .unwrap();
+    db.add_msg_sig_relation(
+        case_a,
+        target,
+        MuxRole::Multiplexed,
+        Some(MuxSelector::Value(0)),
+    )
+    .unwrap();
+    db.add_msg_sig_relation(
+        case_b,
+        target,
+        MuxRole::Multiplexed,
+        Some(MuxSelector::Range { min: 1, max: 3 }),
+    )
+    .unwrap();
+    assert!(matches!(
+        db.add_msg_sig_relation(case_c, target, MuxRole::Multiplexed, Some(MuxSelector::Value(2))),
+        Err(DatabaseError::SignalOverlap { existing, .. }) if existing == case_b
+    ));
+}
+
+#[test]
+fn define_attribute_survives_a_save_and_reparse() {
+    let mut db = parse(CHASSIS);
+    db.define_attribute(
+        "GenMsgILSupport",
+        AttrObject::Message,
+        AttributeDef::Int { min: 0, max: 10 },
+        AttributeValue::Int(3),
+    )
+    .unwrap();
+
+    let text = can_tools::save::to_dbc_string(&db).unwrap();
+    assert!(
+        text.contains("BA_DEF_ BO_ \"GenMsgILSupport\" INT 0 10;"),
+        "{text}"
+    );
+    assert!(
+        text.contains("BA_DEF_DEF_ \"GenMsgILSupport\" 3;"),
+        "{text}"
+    );
+
+    let reparsed = parse(&text);
+    let spec = &reparsed.attr_spec["GenMsgILSupport"];
+    assert_eq!(spec.type_of_object, AttrObject::Message);
+    assert_eq!((spec.int_min, spec.int_max), (Some(0), Some(10)));
+    assert_eq!(spec.default, AttributeValue::Int(3));
+    assert_eq!(
+        reparsed.get_message_by_name("Target").unwrap().attributes["GenMsgILSupport"],
+        AttributeValue::Int(3)
+    );
+}
+
+#[test]
+fn define_attribute_rejects_a_default_outside_the_definition() {
+    let mut db = parse(CHASSIS);
+    assert!(matches!(
+        db.define_attribute(
+            "Level",
+            AttrObject::Signal,
+            AttributeDef::Enum(vec!["Low".into(), "High".into()]),
+            AttributeValue::Enum("Medium".into()),
+        ),
+        Err(DatabaseError::AttributeTypeMismatch { name, .. }) if name == "Level"
+    ));
+    assert!(matches!(
+        db.define_attribute(
+            "Count",
+            AttrObject::Node,
+            AttributeDef::Int { min: 0, max: 10 },
+            AttributeValue::Int(11),
+        ),
+        Err(DatabaseError::AttributeTypeMismatch { .. })
+    ));
+    assert!(!db.attr_spec.contains_key("Level"));
+    assert!(!db.attr_spec.contains_key("Count"));
+}
+
+#[test]
+fn with_message_mut_reads_siblings_while_editing() {
+    let mut db = parse(CHASSIS);
+    let source = db.get_msg_key_by_name("Source").unwrap();
+
+    let signals = db
+        .with_message_mut(source, |message, view| {
+            // the edited message is hidden from the view
+            assert!(view.get_message_by_key(source).is_none());
+            let count = message.signals.len();
+            let others = view.iter_messages().count();
+            message.comment = format!("{count} signal(s), {others} other message(s)");
+            count
+        })
+        .unwrap();
+
+    assert_eq!(signals, 1);
+    assert_eq!(
+        db.get_message_by_key(source).unwrap().comment,
+        "1 signal(s), 2 other message(s)"
+    );
+}
+
+#[test]
+fn with_message_mut_restores_the_message_when_the_closure_panics() {
+    let mut db = parse(CHASSIS);
+    let source = db.get_msg_key_by_name("Source").unwrap();
+
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        db.with_message_mut(source, |message, _| {
+            message.comment = "half done".to_string();
+            panic!("edit failed");
+        })
+    }));
+
+    assert!(outcome.is_err());
+    let message = db.get_message_by_key(source).unwrap();
+    assert_eq!(message.name, "Source");
+    assert_eq!(message.signals.len(), 1);
+    assert_eq!(message.comment, "half done");
+}
+
+#[test]
+fn copy_node_adds_spec_defaults_missing_on_the_source() {
+    let mut db = parse(&format!(
+        "{CHASSIS}BA_DEF_ BU_ \"Layer\" INT 0 9;\n\
+         BA_DEF_DEF_ \"Layer\" 1;\n\
+         BA_ \"Layer\" BU_ Chassis 4;\n"
+    ));
+    // defined after the source node was created, so the source lacks it
+    let zone = AttributeDef::String.to_spec(
+        "Zone",
+        AttrObject::Node,
+        AttributeValue::Str("Front".to_string()),
+    );
+    db.attr_spec.insert("Zone".to_string(), zone);
+    let chassis = db.get_node_key_by_name("Chassis").unwrap();
+    assert!(
+        !db.get_node_by_key(chassis)
+            .unwrap()
+            .attributes
+            .contains_key("Zone")
+    );
+
+    let copy = db.copy_node(chassis).unwrap();
+    let attributes = &db.get_node_by_key(copy).unwrap().attributes;
+    assert_eq!(attributes["Layer"], AttributeValue::Int(4));
+    assert_eq!(attributes["Zone"], AttributeValue::Str("Front".to_string()));
+
+    let reparsed = parse(&can_tools::save::to_dbc_string(&db).unwrap());
+    let attributes = &reparsed
+        .get_node_by_name("Chassis_copy")
+        .unwrap()
+        .attributes;
+    assert_eq!(attributes["Layer"], AttributeValue::Int(4));
+    assert_eq!(attributes["Zone"], AttributeValue::Str("Front".to_string()));
+}
+
+#[test]
+fn name_based_relations_match_the_key_based_path() {
+    let mut by_name = parse(CHASSIS);
+    by_name
+        .add_sig_receiver_node_by_name("Yaw", "Chassis")
+        .unwrap();
+    by_name
+        .add_sender_relation_by_name("Target", "Dash")
+        .unwrap();
+
+    let mut by_key = parse(CHASSIS);
+    let (yaw, chassis) = (
+        by_key.get_sig_key_by_name("Yaw").unwrap(),
+        by_key.get_node_key_by_name("Chassis").unwrap(),
+    );
+    by_key.add_sig_receiver_node(yaw, chassis).unwrap();
+    let (target, dash) = (
+        by_key.get_msg_key_by_name("Target").unwrap(),
+        by_key.get_node_key_by_name("Dash").unwrap(),
+    );
+    by_key.add_sender_relation(target, dash).unwrap();
+
+    assert!(by_name.semantically_equals(&by_key));
+    let dash = by_name.get_node_by_name("Dash").unwrap();
+    assert!(
+        dash.messages_sent
+            .contains(&by_name.get_msg_key_by_name("Target").unwrap())
+    );
+    let chassis = by_name.get_node_by_name("Chassis").unwrap();
+    assert!(
+        chassis
+            .rx_signals
+            .contains(&by_name.get_sig_key_by_name("Yaw").unwrap())
+    );
+}
+
+#[test]
+fn name_based_relations_report_the_unresolved_name() {
+    let mut db = parse(CHASSIS);
+    assert!(matches!(
+        db.add_sig_receiver_node_by_name("Pitch", "Dash"),
+        Err(DatabaseError::SignalNameNotFound { name }) if name == "Pitch"
+    ));
+    assert!(matches!(
+        db.add_sig_receiver_node_by_name("Yaw", "Cluster"),
+        Err(DatabaseError::NodeNameNotFound { name }) if name == "Cluster"
+    ));
+    assert!(matches!(
+        db.add_sender_relation_by_name("Missing", "Dash"),
+        Err(DatabaseError::MessageNameNotFound { name }) if name == "Missing"
+    ));
+    assert!(matches!(
+        db.add_sender_relation_by_name("Target", "Cluster"),
+        Err(DatabaseError::NodeNameNotFound { name }) if name == "Cluster"
+    ));
+}
+
+#[test]
+fn set_signal_layout_moves_into_free_bits_only() {
+    let mut db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Frame: 4 Ecu\n\
+         \x20SG_ Low : 0|8@1+ (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ High : 16|8@1+ (1,0) [0|255] \"\" Ecu\n",
+    );
+    let msg = db.get_msg_key_by_name("Frame").unwrap();
+    let low = db.get_sig_key_by_name("Low").unwrap();
+    let layout = |db: &CanDatabase| {
+        let s = db.get_sig_by_key(low).unwrap();
+        (s.bit_start, s.bit_length, s.endian.clone())
+    };
+
+    db.set_signal_layout(low, 8, 8, Endianness::Intel).unwrap();
+    assert_eq!(layout(&db), (8, 8, Endianness::Intel));
+    let (value, _) = db
+        .decode_signal_labeled(msg, low, &[0x00, 0x2A, 0x00, 0x00])
+        .unwrap();
+    assert_eq!(value, 42.0);
+
+    assert!(matches!(
+        db.set_signal_layout(low, 12, 8, Endianness::Intel),
+        Err(DatabaseError::SignalOverlap { .. })
+    ));
+    assert!(
+        db.set_signal_layout(low, 24, 16, Endianness::Intel)
+            .is_err()
+    );
+    assert_eq!(layout(&db), (8, 8, Endianness::Intel));
+}
+
+#[test]
+fn deduplicate_nodes_merges_case_variants_into_the_first() {
+    let mut db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: ECU1 Spare Dash\n\n\
+         BO_ 1 First: 8 ECU1\n\
+         \x20SG_ Alpha : 0|8@1+ (1,0) [0|255] \"\" Dash\n\n\
+         BO_ 2 Second: 8 Spare\n\
+         \x20SG_ Beta : 0|8@1+ (1,0) [0|255] \"\" Dash,Spare\n",
+    );
+    // case-variant names can only come from hand-edited node data
+    let spare = db.get_node_key_by_name("Spare").unwrap();
+    db.nodes[spare].name = "Ecu1".to_string();
+
+    assert_eq!(db.deduplicate_nodes(), 1);
+    assert_eq!(db.nodes.len(), 2);
+    assert!(db.get_node_by_key(spare).is_none());
+
+    let ecu = db.get_node_key_by_name("ecu1").unwrap();
+    let node = db.get_node_by_key(ecu).unwrap();
+    assert_eq!(node.name, "ECU1");
+    let first = db.get_msg_key_by_name("First").unwrap();
+    let second = db.get_msg_key_by_name("Second").unwrap();
+    assert_eq!(node.messages_sent, [first, second]);
+    assert_eq!(node.rx_signals, [db.get_sig_key_by_name("Beta").unwrap()]);
+    assert_eq!(db.get_message_by_key(second).unwrap().sender_nodes, [ecu]);
+    assert_eq!(db.deduplicate_nodes(), 0);
+}
+
+#[test]
+fn set_signal_sender_narrows_tx_signals_to_the_signal_senders() {
+    let mut db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Engine Gateway Dash\n\n\
+         BO_ 1 Shared: 8 Engine\n\
+         \x20SG_ Torque : 0|8@1+ (1,0) [0|255] \"\" Dash\n\
+         \x20SG_ Route : 8|8@1+ (1,0) [0|255] \"\" Dash\n\n\
+         BO_TX_BU_ 1 :Engine,Gateway;\n",
+    );
+    let engine = db.get_node_key_by_name("Engine").unwrap();
+    let gateway = db.get_node_key_by_name("Gateway").unwrap();
+    let torque = db.get_sig_key_by_name("Torque").unwrap();
+    let route = db.get_sig_key_by_name("Route").unwrap();
+    let tx = |db: &CanDatabase, node| {
+        let mut signals = db.get_node_by_key(node).unwrap().tx_signals.clone();
+        signals.sort_by_key(|&k| db.get_sig_by_key(k).unwrap().name.clone());
+        signals
+    };
+
+    // without per-signal senders every message sender transmits every signal
+    assert_eq!(tx(&db, engine), [route, torque]);
+    assert_eq!(tx(&db, gateway), [route, torque]);
+
+    db.set_signal_sender(torque, engine).unwrap();
+    db.set_signal_sender(route, gateway).unwrap();
+    assert_eq!(tx(&db, engine), [torque]);
+    assert_eq!(tx(&db, gateway), [route]);
+    assert_eq!(db.get_sig_by_key(torque).unwrap().sender_nodes, [engine]);
+
+    assert!(matches!(
+        db.set_signal_sender(CanSignalKey::null(), engine),
+        Err(DatabaseError::SignalMissing { .. })
+    ));
+}
+
+#[test]
+fn sanitize_names_renames_invalid_identifiers_and_updates_lookups() {
+    let mut db = parse(CHASSIS);
+    db.add_node("Front-ECU").unwrap();
+    db.add_message("2nd Frame", 7, 8).unwrap();
+    let speed = loose_signal(&mut db, "Eng Speed!", 0, 16);
+    let clash = loose_signal(&mut db, "Eng Speed?", 16, 16);
+
+    let renamed = db.sanitize_names(NamePolicy::CIdentifier);
+    let pairs: Vec<(&str, &str)> = renamed
+        .iter()
+        .map(|(old, new)| (old.as_str(), new.as_str()))
+        .collect();
+    assert_eq!(
+        pairs,
+        [
+            ("Front-ECU", "Front_ECU"),
+            ("2nd Frame", "_2nd_Frame"),
+            ("Eng Speed!", "Eng_Speed_"),
+            ("Eng Speed?", "Eng_Speed__2"),
+        ]
+    );
+
+    assert_eq!(db.get_sig_key_by_name("Eng_Speed_"), Some(speed));
+    assert_eq!(db.get_sig_key_by_name("eng_speed__2"), Some(clash));
+    assert!(db.get_sig_key_by_name("Eng Speed!").is_none());
+    assert!(db.get_node_by_name("Front_ECU").is_some());
+    assert_eq!(db.get_message_by_name("_2nd_Frame").unwrap().id, 7);
+    assert!(db.sanitize_names(NamePolicy::CIdentifier).is_empty());
+}
+
+#[test]
+fn relation_attributes_set_by_name_are_read_back_resolved() {
+    let mut db = parse(&format!(
+        "{CHASSIS}BA_DEF_REL_ BU_SG_REL_ \"GenSigTimeoutTime\" INT 0 5000;\n\
+         BA_DEF_REL_ BU_BO_REL_ \"GenMsgTimeout\" INT 0 5000;\n\
+         BA_DEF_DEF_REL_ \"GenSigTimeoutTime\" 0;\n\
+         BA_DEF_DEF_REL_ \"GenMsgTimeout\" 0;\n\
+         BA_REL_ \"GenMsgTimeout\" BU_BO_REL_ Dash BO_ 1 250;\n"
+    ));
+
+    db.set_bu_sg_rel_attribute_by_name(
+        "dash",
+        "Yaw",
+        "GenSigTimeoutTime",
+        AttributeValue::Int(100),
+    )
+    .unwrap();
+    let sg: Vec<(&str, &str, Option<&AttributeValue>)> = db
+        .iter_bu_sg_rel()
+        .map(|(node, signal, attrs)| {
+            (
+                node.name.as_str(),
+                signal.name.as_str(),
+                attrs.get("GenSigTimeoutTime"),
+            )
+        })
+        .collect();
+    assert_eq!(sg, [("Dash", "Yaw", Some(&AttributeValue::Int(100)))]);
+
+    let bo: Vec<(&str, &str)> = db
+        .iter_bu_bo_rel()
+        .map(|(node, message, _)| (node.name.as_str(), message.name.as_str()))
+        .collect();
+    assert_eq!(bo, [("Dash", "Source")]);
+
+    assert!(matches!(
+        db.set_bu_sg_rel_attribute_by_name("Cluster", "Yaw", "GenSigTimeoutTime", AttributeValue::Int(1)),
+        Err(DatabaseError::NodeNameNotFound { name }) if name == "Cluster"
+    ));
+    assert!(matches!(
+        db.set_bu_sg_rel_attribute_by_name("Dash", "Roll", "GenSigTimeoutTime", AttributeValue::Int(1)),
+        Err(DatabaseError::SignalNameNotFound { name }) if name == "Roll"
+    ));
+    assert!(matches!(
+        db.set_bu_sg_rel_attribute_by_name(
+            "Dash",
+            "Yaw",
+            "GenSigTimeoutTime",
+            AttributeValue::Str("x".into())
+        ),
+        Err(DatabaseError::AttributeTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn unused_reports_and_prune_unused_removes_isolated_items() {
+    let mut db = parse(CHASSIS);
+    let spare = db.add_node("Spare").unwrap();
+    let orphan = loose_signal(&mut db, "Orphan", 0, 8);
+    let yaw = db.get_sig_key_by_name("Yaw").unwrap();
+    let table = &mut db.get_sig_by_key_mut(yaw).unwrap().value_table;
+    table.insert(-32768, "Min".to_string());
+    table.insert(40000, "Beyond".to_string());
+
+    let report = db.unused();
+    assert_eq!(report.nodes, [spare]);
+    assert_eq!(report.orphan_signals, [orphan]);
+    assert_eq!(report.value_table_out_of_range, [(yaw, 40000)]);
+
+    db.prune_unused();
+    assert!(db.get_node_by_key(spare).is_none());
+    assert!(db.get_sig_by_key(orphan).is_none());
+    assert!(db.get_sig_key_by_name("Orphan").is_none());
+    assert_eq!(db.node_count(), 2);
+    // value tables are only reported
+    assert_eq!(db.get_sig_by_key(yaw).unwrap().value_table.len(), 2);
+
+    let report = db.unused();
+    assert!(report.nodes.is_empty() && report.orphan_signals.is_empty());
+}
+
+#[test]
+fn counts_follow_the_order_views_after_deletions() {
+    let mut db = parse(CHASSIS);
+    loose_signal(&mut db, "Loose", 0, 8);
+    assert_eq!(
+        (
+            db.node_count(),
+            db.message_count(),
+            db.signal_count(),
+            db.bound_signal_count()
+        ),
+        (2, 3, 2, 1)
+    );
+
+    db.delete_signal(db.get_sig_key_by_name("Yaw").unwrap())
+        .unwrap();
+    db.delete_message(db.get_msg_key_by_name("Short").unwrap())
+        .unwrap();
+    assert_eq!(
+        (
+            db.message_count(),
+            db.signal_count(),
+            db.bound_signal_count()
+        ),
+        (2, 1, 0)
+    );
+    assert_eq!(db.signal_count(), db.iter_signals().count());
+}
+
+#[test]
+fn compact_keeps_every_relation_resolvable() {
+    let mut db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Engine Dash Gateway\n\n\
+         BO_ 1 Engine: 8 Engine\n\
+         \x20SG_ A : 0|8@1+ (1,0) [0|255] \"\" Dash\n\
+         \x20SG_ B : 8|8@1+ (1,0) [0|255] \"\" Dash,Gateway\n\
+         \x20SG_ C : 16|8@1+ (1,0) [0|255] \"\" Gateway\n\
+         \x20SG_ D : 24|8@1+ (1,0) [0|255] \"\" Dash\n\n\
+         BO_ 2 Muxed: 8 Gateway\n\
+         \x20SG_ Mode M : 0|4@1+ (1,0) [0|15] \"\" Dash\n\
+         \x20SG_ E m0 : 8|8@1+ (1,0) [0|255] \"\" Dash\n\
+         \x20SG_ F m1 : 8|8@1+ (1,0) [0|255] \"\" Engine\n\
+         \x20SG_ G m1 : 16|8@1+ (1,0) [0|255] \"\" Engine\n",
+    );
+    let old_b = db.get_sig_key_by_name("B").unwrap();
+    let old_mode = db.get_sig_key_by_name("Mode").unwrap();
+    for name in ["A", "C", "E", "G"] {
+        db.delete_signal(db.get_sig_key_by_name(name).unwrap())
+            .unwrap();
+    }
+
+    let remap = db.compact();
+    assert_eq!(db.signals.len(), 4);
+    assert_eq!(db.signal_count(), 4);
+    assert_eq!(remap.signals.len(), 4);
+    assert_eq!(
+        db.get_sig_key_by_name("B"),
+        remap.signals.get(&old_b).copied()
+    );
+
+    for message in db.iter_messages() {
+        for &sig_key in &message.signals {
+            let signal = db.get_sig_by_key(sig_key).unwrap();
+            assert_eq!(
+                db.get_message_by_key(signal.message).unwrap().id,
+                message.id
+            );
+        }
+        for &node_key in message.sender_nodes.iter().chain(&message.receiver_nodes) {
+            assert!(db.get_node_by_key(node_key).is_some());
+        }
+    }
+    for node in db.iter_nodes() {
+        for sig_key in node.rx_signals.iter().chain(&node.tx_signals) {
+            assert!(db.get_sig_by_key(*sig_key).is_some(), "{}", node.name);
+        }
+        for msg_key in &node.messages_sent {
+            assert!(db.get_message_by_key(*msg_key).is_some());
+        }
+    }
+
+    let mode = remap.signals[&old_mode];
+    let muxed = db.get_message_by_name("Muxed").unwrap();
+    assert_eq!(muxed.mux_multiplexors, [mode]);
+    let f = db.get_sig_key_by_name("F").unwrap();
+    assert_eq!(db.get_sig_by_key(f).unwrap().mux_switch, Some(mode));
+    assert_eq!(muxed.mux_cases[&mode][&MuxSelector::Value(1)], [f]);
+    let b = db.get_sig_by_key(remap.signals[&old_b]).unwrap();
+    let receivers: Vec<&str> = b
+        .receiver_nodes
+        .iter()
+        .map(|&k| db.get_node_by_key(k).unwrap().name.as_str())
+        .collect();
+    assert_eq!(receivers, ["Dash", "Gateway"]);
+}
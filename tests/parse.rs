@@ -0,0 +1,429 @@
+//! DBC parsing of individual statements.
+
+mod common;
+
+use can_tools::{
+    DbcParseError,
+    parse::{from_dbc_file, from_dbc_file_strict, from_dbc_file_with_progress, from_reader},
+    types::{
+        attributes::{AttrObject, AttrScope},
+        message::FrameFormat,
+        signal::{Endianness, Signess},
+    },
+};
+use common::parse;
+
+const HEADER: &str = "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n";
+
+/// Parses `body` after a minimal DBC header.
+fn parse_body(body: &str) -> can_tools::types::database::CanDatabase {
+    parse(&format!("{HEADER}{body}"))
+}
+
+#[test]
+fn reversed_ba_def_bounds_are_swapped_with_a_warning() {
+    let db = parse_body(
+        "BA_DEF_ SG_ \"Reversed\" INT 100 0;\n\
+         BA_DEF_ BO_ \"ReversedHex\" HEX 255 16;\n\
+         BA_DEF_ \"Ordered\" FLOAT 0 1.5;\n",
+    );
+
+    let int_spec = &db.attr_spec["Reversed"];
+    assert_eq!((int_spec.int_min, int_spec.int_max), (Some(0), Some(100)));
+    let hex_spec = &db.attr_spec["ReversedHex"];
+    assert_eq!((hex_spec.hex_min, hex_spec.hex_max), (Some(16), Some(255)));
+    let float_spec = &db.attr_spec["Ordered"];
+    assert_eq!(
+        (float_spec.float_min, float_spec.float_max),
+        (Some(0.0), Some(1.5))
+    );
+
+    assert_eq!(db.parse_warnings.len(), 2, "{:?}", db.parse_warnings);
+    assert!(db.parse_warnings[0].contains("Reversed"));
+}
+
+#[test]
+fn vframeformat_overrides_the_dlc_based_frame_type() {
+    let db = parse_body(
+        "BO_ 1 SmallFd: 4 Ecu\n\
+         BO_ 2 Classic: 8 Ecu\n\
+         BA_DEF_ BO_ \"VFrameFormat\" ENUM \"StandardCAN\",\"ExtendedCAN\",\"reserved\",\"reserved\",\
+         \"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\
+         \"reserved\",\"reserved\",\"reserved\",\"StandardCAN_FD\",\"ExtendedCAN_FD\";\n\
+         BA_DEF_DEF_ \"VFrameFormat\" \"StandardCAN\";\n\
+         BA_ \"VFrameFormat\" BO_ 1 15;\n",
+    );
+
+    let small_fd = db.get_message_by_name("SmallFd").unwrap();
+    assert_eq!(small_fd.frame_format, Some(FrameFormat::ExtendedCanFd));
+    assert!(small_fd.is_fd());
+    assert_eq!(small_fd.msgtype, "CAN FD");
+
+    let classic = db.get_message_by_name("Classic").unwrap();
+    assert_eq!(classic.frame_format, None);
+    assert!(!classic.is_fd());
+}
+
+#[test]
+fn from_reader_matches_from_dbc_file() {
+    let fixture: &[u8] = b"VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+        BO_ 1 Climate: 8 Ecu\n SG_ Temp : 0|8@1+ (1,0) [0|255] \"\xB0C\" Vector__XXX\n\n\
+        CM_ SG_ 1 Temp \"Temperatur \xFCber\nzwei Zeilen\";\n";
+    let path = std::env::temp_dir().join(format!("can_tools_{}_reader.dbc", std::process::id()));
+    std::fs::write(&path, fixture).unwrap();
+    let from_file = from_dbc_file(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let from_memory = from_reader(fixture).unwrap();
+    assert!(from_memory.semantically_equals(&from_file));
+
+    let signal = from_memory.get_signal_by_name("Temp").unwrap();
+    assert_eq!(signal.unit_of_measurement, "\u{B0}C");
+    assert_eq!(signal.comment, "Temperatur uber\nzwei Zeilen");
+}
+
+const SIGNAL_TYPES: &str = r#"VAL_TABLE_ OnOff 0 "Off" 1 "On" ;
+
+BO_ 10 Lights: 1 Ecu
+ SG_ Low : 0|1@1+ (1,0) [0|1] "" Vector__XXX
+ SG_ High : 1|1@1+ (1,0) [0|1] "" Vector__XXX
+ SG_ Fog : 2|2@1+ (1,0) [0|3] "" Vector__XXX
+ SG_ Plain : 4|4@1+ (1,0) [0|15] "" Vector__XXX
+
+VAL_ 10 Fog 0 "None" 3 "Both" ;
+
+SGTYPE_ Switch : 1@1+ (1,0) [0|1] "" 0 , OnOff ;
+SGTYPE_ Level : 2@1+ (1,0) [0|3] "" 0 ;
+SGTYPE_VAL_ Level 0 "Off" 1 "Front" 2 "Rear" 3 "Both" ;
+SIG_TYPE_REF_ 10 Low : Switch;
+SIG_TYPE_REF_ 10 High: Switch;
+SIG_TYPE_REF_ 10 Fog : Level;
+"#;
+
+#[test]
+fn effective_value_table_inherits_the_signal_type_table() {
+    let db = parse_body(SIGNAL_TYPES);
+    let table = |name: &str| {
+        let sig_key = db.get_sig_key_by_name(name).unwrap();
+        db.effective_value_table(sig_key)
+            .into_iter()
+            .collect::<Vec<_>>()
+    };
+
+    // inherited through the VAL_TABLE_ named by SGTYPE_
+    let on_off = vec![(0, "Off".to_string()), (1, "On".to_string())];
+    assert_eq!(table("Low"), on_off);
+    assert_eq!(table("High"), on_off);
+    // the signal's own VAL_ wins over SGTYPE_VAL_
+    assert_eq!(
+        table("Fog"),
+        [(0, "None".to_string()), (3, "Both".to_string())]
+    );
+    assert!(table("Plain").is_empty());
+
+    let level = &db.signal_types["Level"];
+    assert_eq!(level.value_table.len(), 4);
+    assert_eq!(level.value_table_name, None);
+    assert_eq!(
+        db.get_signal_by_name("Fog").unwrap().signal_type.as_deref(),
+        Some("Level")
+    );
+
+    // without its own VAL_, the signal falls back to SGTYPE_VAL_
+    let mut db = db;
+    let fog = db.get_sig_key_by_name("Fog").unwrap();
+    db.get_sig_by_key_mut(fog).unwrap().value_table.clear();
+    assert_eq!(
+        db.effective_value_table(fog).get(&1).map(String::as_str),
+        Some("Front")
+    );
+}
+
+#[test]
+fn value_table_references_resolve_regardless_of_order() {
+    let db = parse_body(
+        r#"SIG_TYPE_REF_ 10 Low : Switch;
+SGTYPE_ Switch : 1@1+ (1,0) [0|1] "" 0 , OnOff ;
+
+BO_ 10 Lights: 1 Ecu
+ SG_ Low : 0|1@1+ (1,0) [0|1] "" Vector__XXX
+
+VAL_TABLE_ OnOff 0 "Off" 1 "On" ;
+"#,
+    );
+
+    let low = db.get_sig_key_by_name("Low").unwrap();
+    assert_eq!(
+        db.get_sig_by_key(low).unwrap().signal_type.as_deref(),
+        Some("Switch")
+    );
+    assert_eq!(
+        db.effective_value_table(low)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [(0, "Off".to_string()), (1, "On".to_string())]
+    );
+}
+
+#[test]
+fn gen_msg_send_type_index_resolves_through_the_enum_definition() {
+    let db = parse_body(
+        "BO_ 1 Cyclic: 8 Ecu\n\n\
+         BO_ 2 Spontaneous: 8 Ecu\n\n\
+         BO_ 3 Labelled: 8 Ecu\n\n\
+         BA_DEF_ BO_ \"GenMsgSendType\" ENUM \"Cyclic\",\"Spontaneous\",\"CyclicIfActive\",\
+         \"SpontaneousWithDelay\",\"CyclicAndSpontaneous\",\"CyclicAndSpontaneousWithDelay\",\
+         \"SpontaneousWithRepetition\",\"IfActive\";\n\
+         BA_DEF_ BO_ \"GenMsgCycleTime\" INT 0 1000;\n\
+         BA_DEF_DEF_ \"GenMsgSendType\" \"Cyclic\";\n\
+         BA_DEF_DEF_ \"GenMsgCycleTime\" 0;\n\
+         BA_ \"GenMsgSendType\" BO_ 1 0;\n\
+         BA_ \"GenMsgSendType\" BO_ 2 7;\n\
+         BA_ \"GenMsgSendType\" BO_ 3 \"Cyclic\";\n\
+         BA_ \"GenMsgCycleTime\" BO_ 1 100;\n\
+         BA_ \"GenMsgCycleTime\" BO_ 2 12.5;\n",
+    );
+
+    let send_type = |id: u32| db.get_message_by_id(id).unwrap().send_type.clone();
+    assert_eq!(send_type(1).as_deref(), Some("Cyclic"));
+    assert_eq!(send_type(2).as_deref(), Some("IfActive"));
+    // a quoted label is not a valid ENUM assignment, so the typed field stays unset
+    assert_eq!(send_type(3), None);
+
+    let cycle_time = |id: u32| db.get_message_by_id(id).unwrap().cycle_time;
+    assert_eq!(cycle_time(1), Some(100));
+    // not an integer, so rejected by the INT definition
+    assert_eq!(cycle_time(2), None);
+
+    let cyclic: Vec<u32> = db
+        .cyclic_messages()
+        .into_iter()
+        .map(|k| db.get_message_by_key(k).unwrap().id)
+        .collect();
+    assert_eq!(cyclic, vec![1]);
+}
+
+#[test]
+fn typed_message_fields_fall_back_to_the_raw_value_without_a_definition() {
+    let db = parse_body(
+        "BO_ 1 Free: 8 Ecu\n\n\
+         BA_ \"GenMsgCycleTime\" BO_ 1 250;\n\
+         BA_ \"GenMsgSendType\" BO_ 1 \"Cyclic\";\n",
+    );
+
+    let msg = db.get_message_by_id(1).unwrap();
+    assert_eq!(msg.cycle_time, Some(250));
+    assert_eq!(msg.send_type.as_deref(), Some("Cyclic"));
+    assert!(!msg.attributes.contains_key("GenMsgCycleTime"));
+}
+
+#[test]
+fn sg_sign_char_is_optional_and_may_be_detached() {
+    let db = parse_body(
+        "BO_ 1 Frame: 8 Ecu\n\
+         \x20SG_ NoSign : 48|8@1 (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ Motorola : 48|8@0- (1,0) [-128|127] \"\" Ecu\n\
+         \x20SG_ Intel : 48|8@1+ (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ Detached : 48|8@1 - (2,0) [-256|254] \"\" Ecu\n",
+    );
+
+    let layout = |name: &str| {
+        let signal = db.get_signal_by_name(name).unwrap();
+        (signal.endian.clone(), signal.sign.clone())
+    };
+    assert_eq!(layout("NoSign"), (Endianness::Intel, Signess::Unsigned));
+    assert_eq!(layout("Motorola"), (Endianness::Motorola, Signess::Signed));
+    assert_eq!(layout("Intel"), (Endianness::Intel, Signess::Unsigned));
+    assert_eq!(layout("Detached"), (Endianness::Intel, Signess::Signed));
+    // the detached sign does not shift the rest of the line
+    assert_eq!(db.get_signal_by_name("Detached").unwrap().factor, 2.0);
+
+    assert_eq!(db.parse_warnings.len(), 1, "{:?}", db.parse_warnings);
+    assert!(db.parse_warnings[0].contains("NoSign"));
+}
+
+#[test]
+fn val_ignores_trailing_content_after_the_terminator() {
+    let db = parse_body(
+        "BO_ 960 Frame: 8 Ecu\n\
+         \x20SG_ Sig : 0|1@1+ (1,0) [0|1] \"\" Ecu\n\n\
+         VAL_TABLE_ OnOff 0 \"Off\" 1 \"On\" ; // exported by Tool\n\
+         VAL_ 960 Sig 0 \"Off\" 1 \"On\" ; // exported by Tool\n",
+    );
+
+    let table = &db.get_signal_by_name("Sig").unwrap().value_table;
+    assert_eq!(table.len(), 2);
+    assert_eq!(table[&0], "Off");
+    assert_eq!(table[&1], "On");
+    assert_eq!(db.value_tables["OnOff"].len(), 2);
+}
+
+#[test]
+fn iter_attribute_specs_tags_every_definition_with_its_scope() {
+    let db = parse_body(
+        "BA_DEF_ \"DbVersion\" STRING;\n\
+         BA_DEF_ BO_ \"GenMsgCycleTime\" INT 0 10000;\n\
+         BA_DEF_ BO_ \"GenMsgSendType\" ENUM \"Cyclic\",\"Event\";\n\
+         BA_DEF_ SG_ \"GenSigStartValue\" INT 0 1000;\n\
+         BA_DEF_REL_ BU_SG_REL_ \"GenSigTimeoutTime\" INT 0 5000;\n\
+         BA_DEF_REL_ BU_BO_REL_ \"GenMsgTimeout\" INT 0 5000;\n",
+    );
+
+    let count = |scope: AttrScope| {
+        db.iter_attribute_specs()
+            .filter(|(_, s, _)| *s == scope)
+            .count()
+    };
+    assert_eq!(count(AttrScope::Object(AttrObject::Database)), 1);
+    assert_eq!(count(AttrScope::Object(AttrObject::Message)), 2);
+    assert_eq!(count(AttrScope::Object(AttrObject::Signal)), 1);
+    assert_eq!(count(AttrScope::Object(AttrObject::Node)), 0);
+    assert_eq!(count(AttrScope::NodeSignal), 1);
+    assert_eq!(count(AttrScope::NodeMessage), 1);
+
+    let (name, _, spec) = db
+        .iter_attribute_specs()
+        .find(|(_, scope, _)| *scope == AttrScope::NodeSignal)
+        .unwrap();
+    assert_eq!(name, "GenSigTimeoutTime");
+    assert_eq!(spec.int_max, Some(5000));
+}
+
+#[test]
+fn leading_bom_and_crlf_line_endings_are_ignored() {
+    let mut bytes: Vec<u8> = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend_from_slice(
+        b"VERSION \"1.0\xB0\"\r\n\r\nNS_ :\r\n\r\nBS_:\r\n\r\nBU_: Ecu\r\n\r\n\
+          BO_ 1 Frame: 8 Ecu\r\n SG_ Speed : 0|16@1+ (1,0) [0|65535] \"km/h\" Ecu\r\n",
+    );
+
+    let db = from_reader(bytes.as_slice()).unwrap();
+    // the first line is still Windows-1252 after its byte order mark
+    assert_eq!(db.version, "1.0\u{B0}");
+    let speed = db.get_signal_by_name("Speed").unwrap();
+    assert_eq!(speed.unit_of_measurement, "km/h");
+    assert_eq!(db.get_node_by_name("Ecu").unwrap().name, "Ecu");
+}
+
+#[test]
+fn sig_valtype_codes_map_to_signess() {
+    let db = parse_body(
+        "BO_ 1 Values: 8 Ecu\n\
+         \x20SG_ Integer : 0|16@1- (1,0) [0|0] \"\" Ecu\n\
+         \x20SG_ Single : 32|32@1- (1,0) [0|0] \"\" Ecu\n\
+         \x20SG_ Counter : 16|8@1+ (1,0) [0|0] \"\" Ecu\n\n\
+         BO_ 2 Double: 8 Ecu\n\
+         \x20SG_ Wide : 0|64@1- (1,0) [0|0] \"\" Ecu\n\n\
+         BO_ 3 Legacy: 8 Ecu\n\
+         \x20SG_ Alias : 0|64@1- (1,0) [0|0] \"\" Ecu\n\n\
+         SIG_VALTYPE_ 1 Integer : 0;\n\
+         SIG_VALTYPE_ 1 Counter : 0;\n\
+         SIG_VALTYPE_ 1 Single: 1;\n\
+         SIG_VALTYPE_ 2 Wide :2;\n\
+         SIG_VALTYPE_ 3 Alias : 3;\n",
+    );
+    let sign = |name: &str| {
+        let signal = db.get_signal_by_name(name).unwrap();
+        (signal.sign.clone(), signal.bit_length)
+    };
+    assert_eq!(sign("Integer"), (Signess::Signed, 16));
+    assert_eq!(sign("Counter"), (Signess::Unsigned, 8));
+    assert_eq!(sign("Single"), (Signess::IeeeFloat, 32));
+    assert_eq!(sign("Wide"), (Signess::IeeeDouble, 64));
+    assert_eq!(sign("Alias"), (Signess::IeeeDouble, 64));
+}
+
+#[test]
+fn from_dbc_file_with_progress_ends_at_the_file_length() {
+    let fixture: String = format!(
+        "{HEADER}BO_ 1 Frame: 8 Ecu\n SG_ Speed : 0|16@1+ (1,0) [0|65535] \"\" Ecu\n\n\
+         CM_ BO_ 1 \"first line\nsecond line\";\n"
+    );
+    let path = std::env::temp_dir().join(format!("can_tools_{}_progress.dbc", std::process::id()));
+    std::fs::write(&path, &fixture).unwrap();
+    let mut calls: Vec<(u64, u64)> = Vec::new();
+    let db = from_dbc_file_with_progress(path.to_str().unwrap(), |read, total| {
+        calls.push((read, total))
+    })
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let len = fixture.len() as u64;
+    assert!(!calls.is_empty());
+    assert_eq!(calls.last(), Some(&(len, len)));
+    assert!(calls.windows(2).all(|w| w[0].0 <= w[1].0));
+    assert!(calls.iter().all(|&(_, total)| total == len));
+    assert!(db.get_signal_by_name("Speed").is_some());
+    assert_eq!(
+        db.get_message_by_name("Frame").unwrap().comment,
+        "first line\nsecond line"
+    );
+    assert!(db.unparsed_lines.is_empty(), "{:?}", db.unparsed_lines);
+}
+
+#[test]
+fn undeclared_transmitter_is_created_with_a_warning() {
+    let db = parse_body(
+        "BO_ 1 Known: 8 Ecu\n\n\
+         BO_ 2 Stray: 8 UndeclaredECU\n\n\
+         BO_ 3 Orphan: 8 Vector__XXX\n",
+    );
+
+    let node = db.get_node_key_by_name("UndeclaredECU").unwrap();
+    let stray = db.get_msg_key_by_name("Stray").unwrap();
+    assert_eq!(db.get_message_by_key(stray).unwrap().sender_nodes, [node]);
+    assert_eq!(db.parse_warnings.len(), 1, "{:?}", db.parse_warnings);
+    assert!(db.parse_warnings[0].contains("UndeclaredECU"));
+    // the placeholder never becomes a node
+    assert!(db.get_node_by_name("Vector__XXX").is_none());
+    assert_eq!(db.node_count(), 2);
+}
+
+#[test]
+fn strict_mode_rejects_lines_the_lenient_parser_skips() {
+    let fixture: String = format!(
+        "{HEADER}BO_ 2 Good: 8 Ecu\n\n\
+         BO_ abc Name: 8 Ecu\n SG_ Stray : 0|8@1+ (1,0) [0|255] \"\" Ecu\n"
+    );
+    let path = std::env::temp_dir().join(format!("can_tools_{}_strict.dbc", std::process::id()));
+    std::fs::write(&path, &fixture).unwrap();
+    let strict = from_dbc_file_strict(path.to_str().unwrap());
+    let lenient = from_dbc_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    match strict {
+        Err(DbcParseError::MalformedLines { lines }) => {
+            assert_eq!(lines.len(), 1);
+            assert!(lines[0].1.contains("BO_ abc"), "{lines:?}");
+        }
+        Err(other) => panic!("expected MalformedLines, got {other}"),
+        Ok(_) => panic!("strict mode accepted a malformed BO_"),
+    }
+    let db = lenient.unwrap();
+    assert!(db.get_message_by_name("Name").is_none());
+    // the skipped message's signals do not leak into the previous one
+    assert!(db.get_message_by_name("Good").unwrap().signals.is_empty());
+}
+
+#[test]
+fn malformed_lines_display_handles_an_empty_list() {
+    let empty = DbcParseError::MalformedLines { lines: Vec::new() };
+    assert_eq!(empty.to_string(), "0 malformed line(s)");
+
+    let one = DbcParseError::MalformedLines {
+        lines: vec![(7, "BO_ abc".to_string())],
+    };
+    assert_eq!(
+        one.to_string(),
+        "1 malformed line(s), first at line 7: BO_ abc"
+    );
+}
+
+#[test]
+fn stray_text_is_reported_instead_of_kept() {
+    let db = parse_body("BO_ 1 Frame: 8 Ecu\n\nline two\";\nSIG_GROUP_ 1 Group 1 : Speed;\n");
+
+    assert_eq!(db.unparsed_lines, ["SIG_GROUP_ 1 Group 1 : Speed;"]);
+    assert_eq!(db.parse_warnings.len(), 1, "{:?}", db.parse_warnings);
+    assert!(db.parse_warnings[0].contains("line two"));
+}
@@ -0,0 +1,630 @@
+//! Read-only queries over a parsed database.
+
+mod common;
+
+use can_tools::{
+    analysis::id_collisions,
+    types::{
+        database::{
+            CanDatabase, CanNodeKey, CanSignalKey, MessageSortKey, NodeSortKey, SignalSortKey,
+            normalize_id_hex,
+        },
+        message::CanMessage,
+    },
+};
+use common::parse;
+
+const BODY: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Body Dash
+
+BO_ 200 Doors: 8 Body
+ SG_ Rear : 16|8@1+ (1,0) [0|255] "%" Dash
+ SG_ Front : 0|8@1+ (1,0) [0|255] "%" Dash
+ SG_ Trunk : 8|4@1+ (1,0) [0|15] "" Dash
+"#;
+
+const UNITS: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Ecu
+
+BO_ 300 Drive: 8 Ecu
+ SG_ WheelSpeed : 0|8@1+ (1,0) [0|100] "km/h" Vector__XXX
+ SG_ VehicleSpeed : 8|8@1+ (1,100) [100|200] "KM/H" Vector__XXX
+ SG_ EngineSpeed : 16|16@1+ (1,201) [201|300] "rpm" Vector__XXX
+"#;
+
+const NETWORK: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Engine Gateway Dash Brake
+
+BO_ 10 EngineData: 8 Engine
+ SG_ Rpm : 0|16@1+ (1,0) [0|8000] "rpm" Dash,Gateway
+ SG_ Coolant : 16|8@1+ (1,-40) [-40|215] "degC" Dash
+
+BO_ 20 BrakeData: 8 Brake
+ SG_ Pressure : 0|16@1+ (0.1,0) [0|6553.5] "bar" Gateway
+
+BO_ 30 GatewayData: 8 Gateway
+ SG_ Status : 0|8@1+ (1,0) [0|255] "" Dash
+
+BO_ 2463978723 Diagnostics: 8 Gateway
+
+BO_TX_BU_ 30 :Gateway,Engine;
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_DEF_DEF_ "GenMsgCycleTime" 0;
+BA_ "GenMsgCycleTime" BO_ 10 100;
+"#;
+
+fn names<'a>(
+    signals: impl Iterator<Item = &'a can_tools::types::signal::CanSignal>,
+) -> Vec<&'a str> {
+    signals.map(|s| s.name.as_str()).collect()
+}
+
+#[test]
+fn iter_message_signals_follows_message_signal_order() {
+    let mut db = parse(BODY);
+    let msg = db.get_msg_key_by_name("Doors").unwrap();
+    // the parser sorts message signals by name
+    assert_eq!(
+        names(db.iter_message_signals(msg)),
+        ["Front", "Rear", "Trunk"]
+    );
+
+    db.get_message_by_key_mut(msg).unwrap().signals.reverse();
+    assert_eq!(
+        names(db.iter_message_signals(msg)),
+        ["Trunk", "Rear", "Front"]
+    );
+}
+
+#[test]
+fn iter_message_signals_by_bit_sorts_by_start_bit() {
+    let db = parse(BODY);
+    let msg = db.get_msg_key_by_name("Doors").unwrap();
+    assert_eq!(
+        names(db.iter_message_signals_by_bit(msg)),
+        ["Front", "Trunk", "Rear"]
+    );
+}
+
+#[test]
+fn node_summary_counts_a_message_once_per_receiver() {
+    let db = parse(BODY);
+    let msg = db.get_msg_key_by_name("Doors").unwrap();
+
+    let dash = db.node_summary(db.get_node_key_by_name("Dash").unwrap());
+    assert_eq!(dash.messages_received, [msg]);
+    assert_eq!(dash.rx_signal_count, 3);
+    assert!(dash.messages_sent.is_empty());
+    assert_eq!(dash.rx_units.iter().collect::<Vec<_>>(), ["%"]);
+
+    let body = db.node_summary(db.get_node_key_by_name("Body").unwrap());
+    assert_eq!(body.messages_sent, [msg]);
+    assert_eq!(body.tx_signal_count, 3);
+    assert!(body.messages_received.is_empty());
+}
+
+fn signal_names(db: &CanDatabase, keys: &[CanSignalKey]) -> Vec<String> {
+    keys.iter()
+        .map(|&k| db.get_sig_by_key(k).unwrap().name.clone())
+        .collect()
+}
+
+#[test]
+fn find_signals_by_unit_matches_trimmed_and_case_insensitive() {
+    let mut db = parse(UNITS);
+    db.get_signal_by_name_mut("WheelSpeed")
+        .unwrap()
+        .unit_of_measurement = " km/h ".into();
+    assert_eq!(
+        signal_names(&db, &db.find_signals_by_unit("km/h")),
+        ["VehicleSpeed", "WheelSpeed"]
+    );
+    assert_eq!(
+        signal_names(&db, &db.find_signals_by_unit(" RPM")),
+        ["EngineSpeed"]
+    );
+    assert!(db.find_signals_by_unit("m/s").is_empty());
+}
+
+#[test]
+fn find_signals_in_range_overlap_is_inclusive() {
+    let db = parse(UNITS);
+    assert_eq!(
+        signal_names(&db, &db.find_signals_in_range(100.0, 100.0)),
+        ["VehicleSpeed", "WheelSpeed"]
+    );
+    assert_eq!(
+        signal_names(&db, &db.find_signals_in_range(200.5, 201.0)),
+        ["EngineSpeed"]
+    );
+    assert!(db.find_signals_in_range(301.0, 400.0).is_empty());
+}
+
+#[test]
+fn ambiguous_frame_formats_flags_long_classic_frames() {
+    let db = parse(
+        r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Ecu
+
+BO_ 1 LongClassic: 12 Ecu
+BO_ 2 LongUntagged: 12 Ecu
+BO_ 3 ShortFd: 4 Ecu
+BO_ 4 Classic: 8 Ecu
+
+BA_DEF_ BO_ "VFrameFormat" INT 0 15;
+BA_DEF_DEF_ "VFrameFormat" 0;
+BA_ "VFrameFormat" BO_ 1 0;
+BA_ "VFrameFormat" BO_ 3 14;
+"#,
+    );
+    assert_eq!(
+        db.ambiguous_frame_formats(),
+        [db.get_msg_key_by_name("LongClassic").unwrap()]
+    );
+}
+
+#[test]
+fn subset_keeps_one_message_its_signals_and_involved_nodes() {
+    let db = parse(NETWORK);
+    let engine = db.get_msg_key_by_name("EngineData").unwrap();
+
+    let sub = db.subset(&[engine]);
+    assert_eq!(sub.message_count(), 1);
+    let message = sub.get_message_by_name("EngineData").unwrap();
+    assert_eq!(message.cycle_time, Some(100));
+    assert_eq!(
+        names(sub.iter_message_signals(sub.get_msg_key_by_name("EngineData").unwrap())),
+        ["Coolant", "Rpm"]
+    );
+    assert_eq!(sub.signal_count(), 2);
+    assert!(sub.attr_spec.contains_key("GenMsgCycleTime"));
+
+    let node_names: Vec<&str> = sub.iter_nodes().map(|n| n.name.as_str()).collect();
+    assert_eq!(node_names, ["Dash", "Engine", "Gateway"]);
+    assert!(sub.get_node_by_name("Brake").is_none());
+
+    // keys are fresh and the lookups are rebuilt
+    let rpm = sub.get_sig_key_by_name("Rpm").unwrap();
+    assert_eq!(
+        sub.get_sig_by_key(rpm).unwrap().message,
+        sub.get_msg_key_by_name("EngineData").unwrap()
+    );
+    assert_eq!(sub.get_message_by_id(10).unwrap().name, "EngineData");
+}
+
+#[test]
+fn messages_received_by_node_lists_each_message_once_in_order() {
+    let db = parse(NETWORK);
+    let message_names = |node: &str| -> Vec<String> {
+        let node_key = db.get_node_key_by_name(node).unwrap();
+        db.messages_received_by_node(node_key)
+            .into_iter()
+            .map(|k| db.get_message_by_key(k).unwrap().name.clone())
+            .collect()
+    };
+
+    // two signals of EngineData reach Dash, listed once; messages follow messages_order
+    assert_eq!(message_names("Dash"), ["EngineData", "GatewayData"]);
+    assert_eq!(message_names("Gateway"), ["BrakeData", "EngineData"]);
+    assert!(message_names("Brake").is_empty());
+}
+
+#[test]
+fn get_msg_key_by_id_hex_accepts_every_documented_spelling() {
+    let db = parse(NETWORK);
+    let diagnostics = db.get_msg_key_by_name("Diagnostics").unwrap();
+
+    for spelling in ["12dd54e3", "0x12dd54e3", "12DD54E3x", "0x12DD54E3"] {
+        assert_eq!(normalize_id_hex(spelling), "0x12DD54E3");
+        assert_eq!(
+            db.get_msg_key_by_id_hex(spelling),
+            Some(diagnostics),
+            "{spelling}"
+        );
+    }
+
+    // standard IDs are padded to three digits
+    assert_eq!(normalize_id_hex("a"), "0x00A");
+    assert_eq!(
+        db.get_msg_key_by_id_hex("0XA"),
+        db.get_msg_key_by_name("EngineData")
+    );
+    assert_eq!(normalize_id_hex(" zz "), "ZZ");
+    assert_eq!(db.get_msg_key_by_id_hex("zz"), None);
+}
+
+#[test]
+fn split_by_sender_puts_a_shared_message_in_each_sender_database() {
+    let db = parse(NETWORK);
+    let split = db.split_by_sender();
+
+    assert_eq!(
+        split.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["Brake", "Engine", "Gateway"]
+    );
+    let message_names = |node: &str| -> Vec<&str> {
+        split[node]
+            .iter_messages()
+            .map(|m| m.name.as_str())
+            .collect()
+    };
+    assert_eq!(message_names("Engine"), ["EngineData", "GatewayData"]);
+    assert_eq!(message_names("Gateway"), ["Diagnostics", "GatewayData"]);
+    assert_eq!(message_names("Brake"), ["BrakeData"]);
+
+    let shared = split["Engine"].get_message_by_name("GatewayData").unwrap();
+    assert_eq!(shared.signals.len(), 1);
+    assert!(split["Gateway"].get_signal_by_name("Status").is_some());
+}
+
+#[test]
+fn signals_in_byte_range_finds_a_motorola_signal_from_both_bytes() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Grid: 8 Ecu\n\
+         \x20SG_ Cross : 3|8@0+ (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ Tail : 24|8@1+ (1,0) [0|255] \"\" Ecu\n",
+    );
+    let msg = db.get_msg_key_by_name("Grid").unwrap();
+    let query = |start: u16, end: u16| -> Vec<String> {
+        signal_names(&db, &db.signals_in_byte_range(msg, start, end))
+    };
+
+    // bits 3..0 of byte 0, then bits 7..4 of byte 1
+    assert_eq!(query(0, 0), ["Cross"]);
+    assert_eq!(query(1, 1), ["Cross"]);
+    assert!(query(2, 2).is_empty());
+    assert_eq!(query(1, 3), ["Cross", "Tail"]);
+    assert!(query(8, 63).is_empty());
+}
+
+const SEARCH: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Ecu
+
+BO_ 1 Engine_Status: 8 Ecu
+ SG_ StatusByte : 0|8@1+ (1,0) [0|255] "" Ecu
+
+BO_ 2 status_flags: 8 Ecu
+ SG_ Flag : 0|1@1+ (1,0) [0|1] "" Ecu
+
+BO_ 3 Temperature: 8 Ecu
+ SG_ Coolant_STATUS : 0|8@1+ (1,0) [0|255] "" Ecu
+"#;
+
+fn message_names(
+    db: &CanDatabase,
+    keys: &[can_tools::types::database::CanMessageKey],
+) -> Vec<String> {
+    keys.iter()
+        .map(|&k| db.get_message_by_key(k).unwrap().name.clone())
+        .collect()
+}
+
+#[test]
+fn find_messages_matching_is_a_case_insensitive_substring_search() {
+    let db = parse(SEARCH);
+    assert_eq!(
+        message_names(&db, &db.find_messages_matching("status")),
+        ["Engine_Status", "status_flags"]
+    );
+    assert_eq!(
+        signal_names(&db, &db.find_signals_matching("Status")),
+        ["Coolant_STATUS", "StatusByte"]
+    );
+    assert!(db.find_messages_matching("missing").is_empty());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn find_messages_regex_follows_messages_order() {
+    let db = parse(SEARCH);
+    let re = regex::Regex::new("(?i)^.*status$").unwrap();
+    assert_eq!(
+        message_names(&db, &db.find_messages_regex(&re)),
+        ["Engine_Status"]
+    );
+    assert_eq!(
+        signal_names(&db, &db.find_signals_regex(&re)),
+        ["Coolant_STATUS"]
+    );
+}
+
+#[test]
+fn occupied_masks_follow_the_signal_layout() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Bits: 8 Ecu\n\
+         \x20SG_ Nibble : 4|4@1+ (1,0) [0|15] \"\" Ecu\n\
+         \x20SG_ Word : 23|12@0+ (1,0) [0|4095] \"\" Ecu\n\
+         \x20SG_ Beyond : 32|16@1+ (1,0) [0|65535] \"\" Ecu\n",
+    );
+    let mask = |name: &str, dlc: u16| db.get_signal_by_name(name).unwrap().occupied_mask(dlc);
+
+    assert_eq!(mask("Nibble", 8), 0xF0);
+    // Motorola: byte2[7..0] ++ byte3[7..4]
+    assert_eq!(mask("Word", 8), 0xF0FF_0000);
+    // bits past the DLC are dropped
+    assert_eq!(mask("Beyond", 8), 0xFFFF_0000_0000);
+    assert_eq!(mask("Beyond", 5), 0xFF_0000_0000);
+
+    let msg = db.get_msg_key_by_name("Bits").unwrap();
+    assert_eq!(db.message_used_mask(msg), 0xFFFF_F0FF_00F0);
+}
+
+#[test]
+fn sort_by_keys_reorder_iteration() {
+    let mut db = parse(NETWORK);
+    let message_names =
+        |db: &CanDatabase| -> Vec<String> { db.iter_messages().map(|m| m.name.clone()).collect() };
+
+    db.sort_messages_by(MessageSortKey::IdAsc);
+    let ids: Vec<u32> = db.iter_messages().map(|m| m.id).collect();
+    assert!(ids.is_sorted(), "{ids:?}");
+    assert_eq!(
+        message_names(&db),
+        ["EngineData", "BrakeData", "GatewayData", "Diagnostics"]
+    );
+
+    db.sort_messages_by(MessageSortKey::IdDesc);
+    assert_eq!(
+        message_names(&db),
+        ["Diagnostics", "GatewayData", "BrakeData", "EngineData"]
+    );
+
+    // equal payload lengths fall back to the name order
+    db.sort_messages_by(MessageSortKey::DlcAsc);
+    assert_eq!(
+        message_names(&db),
+        ["BrakeData", "Diagnostics", "EngineData", "GatewayData"]
+    );
+
+    db.sort_signals_by(SignalSortKey::StartBitAsc);
+    assert_eq!(
+        names(db.iter_signals()),
+        ["Pressure", "Rpm", "Status", "Coolant"]
+    );
+
+    db.sort_nodes_by(NodeSortKey::NameDesc);
+    let node_names: Vec<&str> = db.iter_nodes().map(|n| n.name.as_str()).collect();
+    assert_eq!(node_names, ["Gateway", "Engine", "Dash", "Brake"]);
+}
+
+#[test]
+fn message_coverage_reports_free_bits_and_gaps() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Sparse: 8 Ecu\n\
+         \x20SG_ Word : 8|16@1+ (1,0) [0|65535] \"\" Ecu\n\n\
+         BO_ 2 Muxed: 8 Ecu\n\
+         \x20SG_ Mode M : 0|4@1+ (1,0) [0|15] \"\" Ecu\n\
+         \x20SG_ CaseA m0 : 8|8@1+ (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ CaseB m1 : 12|8@1+ (1,0) [0|255] \"\" Ecu\n\n\
+         BO_ 3 Short: 2 Ecu\n",
+    );
+
+    let sparse = db.message_coverage(db.get_msg_key_by_name("Sparse").unwrap());
+    assert_eq!(
+        (sparse.used_bits, sparse.free_bits, sparse.total_bits),
+        (16, 48, 64)
+    );
+    assert_eq!(sparse.gaps, [(0, 7), (24, 63)]);
+
+    // multiplexed cases count as their union
+    let muxed = db.message_coverage(db.get_msg_key_by_name("Muxed").unwrap());
+    assert_eq!((muxed.used_bits, muxed.free_bits), (16, 48));
+    assert_eq!(muxed.gaps, [(4, 7), (20, 63)]);
+
+    let short = db.message_coverage(db.get_msg_key_by_name("Short").unwrap());
+    assert_eq!(short.gaps, [(0, 15)]);
+
+    assert_eq!(db.total_payload_bits(), 64 + 64 + 16);
+}
+
+#[test]
+fn only_messages_mixing_byte_orders_are_flagged() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Mixed: 8 Ecu\n\
+         \x20SG_ Little : 0|8@1+ (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ Big : 15|8@0+ (1,0) [0|255] \"\" Ecu\n\n\
+         BO_ 2 Uniform: 8 Ecu\n\
+         \x20SG_ First : 0|8@1+ (1,0) [0|255] \"\" Ecu\n\
+         \x20SG_ Second : 8|8@1+ (1,0) [0|255] \"\" Ecu\n\n\
+         BO_ 3 Empty: 8 Ecu\n",
+    );
+
+    assert_eq!(db.endianness_histogram(), (3, 1));
+    assert_eq!(
+        db.find_mixed_endianness_messages(),
+        [db.get_msg_key_by_name("Mixed").unwrap()]
+    );
+}
+
+#[test]
+fn simulate_schedule_includes_both_ends_and_orders_ties_by_id() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 512 Fast: 8 Ecu\n\n\
+         BO_ 256 Slow: 8 Ecu\n\n\
+         BO_ 768 Event: 8 Ecu\n\n\
+         BA_DEF_ BO_ \"GenMsgCycleTime\" INT 0 10000;\n\
+         BA_DEF_DEF_ \"GenMsgCycleTime\" 0;\n\
+         BA_ \"GenMsgCycleTime\" BO_ 512 10;\n\
+         BA_ \"GenMsgCycleTime\" BO_ 256 20;\n",
+    );
+    let fast = db.get_msg_key_by_name("Fast").unwrap();
+    let slow = db.get_msg_key_by_name("Slow").unwrap();
+
+    assert_eq!(
+        db.simulate_schedule(40),
+        [
+            (0, slow),
+            (0, fast),
+            (10, fast),
+            (20, slow),
+            (20, fast),
+            (30, fast),
+            (40, slow),
+            (40, fast),
+        ]
+    );
+    assert_eq!(db.simulate_schedule(0), [(0, slow), (0, fast)]);
+}
+
+#[test]
+fn id_collisions_lists_every_database_sharing_an_id() {
+    let database = |name: &str, messages: &str| {
+        parse(&format!(
+            "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n{messages}\n\
+             BA_DEF_ \"DBName\" STRING;\n\
+             BA_DEF_DEF_ \"DBName\" \"\";\n\
+             BA_ \"DBName\" \"{name}\";\n"
+        ))
+    };
+    let body = database("Body", "BO_ 256 Doors: 8 Ecu\n\nBO_ 512 Lights: 8 Ecu\n");
+    let chassis = database(
+        "Chassis",
+        "BO_ 256 Wheels: 8 Ecu\n\nBO_ 768 Brakes: 8 Ecu\n",
+    );
+    let infotainment = database("Infotainment", "BO_ 1024 Radio: 8 Ecu\n");
+
+    assert_eq!(
+        id_collisions(&[&body, &chassis, &infotainment]),
+        [(0x100, vec!["Body".to_string(), "Chassis".to_string()])]
+    );
+    assert!(id_collisions(&[&body, &infotainment]).is_empty());
+}
+
+#[test]
+fn messages_touching_node_splits_sent_and_received() {
+    let db = parse(NETWORK);
+    let touching = |node: &str| -> (Vec<String>, Vec<String>) {
+        let node_key = db.get_node_key_by_name(node).unwrap();
+        let touching = db.messages_touching_node(node_key);
+        let names = |keys: Vec<_>| {
+            keys.into_iter()
+                .map(|k| db.get_message_by_key(k).unwrap().name.clone())
+                .collect()
+        };
+        (names(touching.sent), names(touching.received))
+    };
+
+    let (sent, received) = touching("Gateway");
+    assert_eq!(sent, ["Diagnostics", "GatewayData"]);
+    assert_eq!(received, ["BrakeData", "EngineData"]);
+    assert!(sent.iter().all(|name| !received.contains(name)));
+
+    // a BO_TX_BU_ transmitter sends without owning the message
+    assert_eq!(
+        touching("Engine"),
+        (
+            vec!["EngineData".to_string(), "GatewayData".to_string()],
+            vec![]
+        )
+    );
+    assert!(
+        db.messages_touching_node(CanNodeKey::default())
+            .sent
+            .is_empty()
+    );
+}
+
+#[test]
+fn can_and_canfd_iterators_split_a_mixed_database() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Classic: 8 Ecu\n\n\
+         BO_ 2 FdShort: 8 Ecu\n\n\
+         BO_ 3 FdLong: 64 Ecu\n\n\
+         BO_ 2147483652 ClassicExt: 8 Ecu\n\n\
+         BA_DEF_ BO_ \"VFrameFormat\" ENUM \"StandardCAN\",\"ExtendedCAN\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"reserved\",\"StandardCAN_FD\",\"ExtendedCAN_FD\";\n\
+         BA_DEF_DEF_ \"VFrameFormat\" \"StandardCAN\";\n\
+         BA_ \"VFrameFormat\" BO_ 2 14;\n\
+         BA_ \"VFrameFormat\" BO_ 3 14;\n",
+    );
+    let ids =
+        |messages: Vec<&CanMessage>| -> Vec<u32> { messages.into_iter().map(|m| m.id).collect() };
+    assert_eq!(ids(db.iter_can_messages().collect()), [1, 0x8000_0004]);
+    assert_eq!(ids(db.iter_canfd_messages().collect()), [3, 2]);
+}
+
+#[test]
+fn signal_ranges_apply_factor_and_offset_to_the_raw_bounds() {
+    let db = parse(
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: Ecu\n\n\
+         BO_ 1 Frame: 8 Ecu\n\
+         \x20SG_ Signed : 0|8@1- (0.5,-10) [-74|53.5] \"\" Vector__XXX\n\
+         \x20SG_ Inverted : 8|8@1+ (-1,0) [-255|0] \"\" Vector__XXX\n",
+    );
+    let signed = db.get_sig_key_by_name("Signed").unwrap();
+    let inverted = db.get_sig_key_by_name("Inverted").unwrap();
+
+    assert_eq!(
+        db.signal_ranges(),
+        [
+            (inverted, 0, 255, -255.0, 0.0),
+            (signed, -128, 127, -74.0, 53.5),
+        ]
+    );
+}
+
+#[test]
+fn signals_grouped_by_message_matches_each_message() {
+    let db = parse(NETWORK);
+    let grouped = db.signals_grouped_by_message();
+
+    assert_eq!(grouped.len(), db.message_count());
+    for message in db.iter_messages() {
+        let key = db.get_msg_key_by_name(&message.name).unwrap();
+        assert_eq!(grouped[&key], message.signals, "{}", message.name);
+    }
+    let diagnostics = db.get_msg_key_by_name("Diagnostics").unwrap();
+    assert!(grouped[&diagnostics].is_empty());
+}
+
+#[test]
+fn to_tree_string_outlines_nodes_then_messages() {
+    let db = parse(BODY);
+    let tree: String = db.to_tree_string();
+
+    assert_eq!(
+        tree,
+        "Nodes:\n\
+         \x20 Body\n\
+         \x20   tx: Doors (0x0C8)\n\
+         \x20 Dash\n\
+         \x20   rx: Doors (0x0C8)\n\
+         Messages:\n\
+         \x20 Doors 0x0C8 [8 bytes]\n\
+         \x20   Front 0|8 Intel Unsigned\n\
+         \x20   Rear 16|8 Intel Unsigned\n\
+         \x20   Trunk 8|4 Intel Unsigned\n"
+    );
+    assert_eq!(tree, db.to_tree_string());
+}
@@ -0,0 +1,401 @@
+//! Serialization to DBC text.
+
+mod common;
+
+use can_tools::parse::from_dbc_file;
+use can_tools::save::{SaveOptions, save_to_file_with, to_dbc_string};
+use can_tools::types::{database::BusType, message::FrameFormat};
+use common::parse;
+use std::{fs, path::PathBuf};
+
+const ORPHANS: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_:
+
+BO_ 1 Orphan: 8 Vector__XXX
+ SG_ Lonely : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+"#;
+
+/// Returns a per-test path under the system temp directory.
+fn temp_dbc(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("can_tools_{}_{name}.dbc", std::process::id()))
+}
+
+#[test]
+fn save_to_file_with_uses_the_custom_placeholder() {
+    let db = parse(ORPHANS);
+    let path = temp_dbc("placeholder");
+    let options = SaveOptions {
+        empty_node_placeholder: "NoNode".to_string(),
+        ..SaveOptions::default()
+    };
+
+    save_to_file_with(path.to_str().unwrap(), &db, &options).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(text.contains("BO_ 1 Orphan: 8 NoNode"), "{text}");
+    let signal_line = text.lines().find(|l| l.contains("SG_ Lonely")).unwrap();
+    assert!(signal_line.ends_with(" NoNode"), "{signal_line}");
+    assert!(!text.contains("Vector__XXX"), "{text}");
+}
+
+#[test]
+fn frame_format_is_written_back_with_its_definition() {
+    let mut db = parse(ORPHANS);
+    db.get_message_by_name_mut("Orphan")
+        .unwrap()
+        .set_frame_format(FrameFormat::ExtendedCanFd);
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.contains("BA_DEF_ BO_ \"VFrameFormat\" ENUM"), "{text}");
+    assert!(text.contains("BA_ \"VFrameFormat\" BO_ 1 15;"), "{text}");
+
+    let reparsed = parse(&text);
+    let message = reparsed.get_message_by_name("Orphan").unwrap();
+    assert_eq!(message.frame_format, Some(FrameFormat::ExtendedCanFd));
+}
+
+#[test]
+fn escaped_comment_round_trips() {
+    let comment_line = r#"CM_ BO_ 1 "He said \"hi\"; bye";"#;
+    let db = parse(&format!("{ORPHANS}\n{comment_line}\n"));
+    let comment = &db.get_message_by_name("Orphan").unwrap().comment;
+    assert_eq!(comment, r#"He said "hi"; bye"#);
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.contains(comment_line), "{text}");
+    let reparsed = parse(&text);
+    assert_eq!(
+        &reparsed.get_message_by_name("Orphan").unwrap().comment,
+        comment
+    );
+    assert!(reparsed.semantically_equals(&db));
+}
+
+#[test]
+fn global_value_tables_round_trip() {
+    let db = parse(&ORPHANS.replace(
+        "BU_:",
+        "VAL_TABLE_ OnOff 0 \"Off\" 1 \"On\" ;\nVAL_TABLE_ Gear 0 \"Park\" 1 \"Drive\" ;\n\nBU_:",
+    ));
+    assert_eq!(db.value_tables.len(), 2);
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(
+        text.contains("VAL_TABLE_ OnOff 0 \"Off\" 1 \"On\" ;"),
+        "{text}"
+    );
+    assert!(
+        text.contains("VAL_TABLE_ Gear 0 \"Park\" 1 \"Drive\" ;"),
+        "{text}"
+    );
+    assert_eq!(parse(&text).value_tables, db.value_tables);
+}
+
+#[test]
+fn matching_value_table_finds_an_identical_global_table() {
+    let mut db = parse(&ORPHANS.replace("BU_:", "VAL_TABLE_ OnOff 0 \"Off\" 1 \"On\" ;\n\nBU_:"));
+    let lonely = db.get_sig_key_by_name("Lonely").unwrap();
+    assert_eq!(db.matching_value_table(lonely), None);
+
+    let table = db.value_tables["OnOff"].clone();
+    db.get_sig_by_key_mut(lonely).unwrap().value_table = table;
+    assert_eq!(db.matching_value_table(lonely), Some("OnOff"));
+}
+
+#[test]
+fn bit_timing_round_trips_through_the_bs_section() {
+    let db = parse(&ORPHANS.replace("BS_:\n", "BS_: 500000:1,2\n"));
+    let bit_timing = db.bit_timing.unwrap();
+    assert_eq!(
+        (bit_timing.baudrate, bit_timing.btr1, bit_timing.btr2),
+        (500000, 1, 2)
+    );
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.lines().any(|l| l == "BS_: 500000:1,2"), "{text}");
+
+    // an empty section stays empty
+    let text = to_dbc_string(&parse(ORPHANS)).unwrap();
+    assert!(text.lines().any(|l| l == "BS_:"), "{text}");
+}
+
+#[test]
+fn vector_xxx_never_becomes_a_node_across_round_trips() {
+    let db = parse(&ORPHANS.replace("BU_:\n", "BU_: Vector__XXX\n"));
+    assert_eq!(db.node_count(), 0);
+    assert!(db.get_node_by_name("Vector__XXX").is_none());
+    let lonely = db.get_signal_by_name("Lonely").unwrap();
+    assert!(lonely.receiver_nodes.is_empty());
+    assert!(
+        db.get_message_by_name("Orphan")
+            .unwrap()
+            .sender_nodes
+            .is_empty()
+    );
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.lines().any(|l| l.trim_end() == "BU_:"), "{text}");
+    let reparsed = parse(&text);
+    assert_eq!(reparsed.node_count(), 0);
+    assert!(
+        reparsed
+            .get_signal_by_name("Lonely")
+            .unwrap()
+            .receiver_nodes
+            .is_empty()
+    );
+}
+
+#[test]
+fn to_dbc_string_matches_the_file_contents() {
+    let db = parse(&ORPHANS.replace("BU_:\n", "BU_: Ecu\n"));
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.starts_with("VERSION \""), "{text}");
+    assert!(
+        text.lines().any(|l| l == "BO_ 1 Orphan: 8 Vector__XXX"),
+        "{text}"
+    );
+
+    let path = temp_dbc("to_string");
+    fs::write(&path, &text).unwrap();
+    let reparsed = from_dbc_file(path.to_str().unwrap()).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(reparsed.semantically_equals(&db));
+}
+
+#[test]
+fn unsupported_statements_are_written_back_verbatim() {
+    let sgtype = "SGTYPE_ Switch : 1@1+ (1,0) [0|1] \"\" 0 ;";
+    let category = "CAT_ BO_ 1 2;";
+    let db = parse(&format!("{ORPHANS}\n{sgtype}\n{category}\n"));
+    assert_eq!(db.unparsed_lines, [sgtype, category]);
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.lines().any(|l| l == sgtype), "{text}");
+    assert!(text.lines().any(|l| l == category), "{text}");
+
+    // a second round-trip does not duplicate them
+    let text = to_dbc_string(&parse(&text)).unwrap();
+    assert_eq!(text.lines().filter(|l| *l == sgtype).count(), 1, "{text}");
+}
+
+const RICH: &str = r#"VERSION "1.2"
+
+NS_ :
+
+BS_:
+
+BU_: Engine Dash
+
+BO_ 100 EngineData: 8 Engine
+ SG_ Rpm : 0|16@1+ (0.25,0) [0|16383.75] "rpm" Dash
+ SG_ Gear : 16|4@1+ (1,0) [0|15] "" Dash
+
+BO_ 2566914560 Muxed: 8 Engine
+ SG_ Mode M : 0|4@1+ (1,0) [0|15] "" Dash
+ SG_ Temp m0 : 8|8@1- (1,-40) [-168|87] "degC" Dash
+ SG_ Pressure m1 : 15|8@0+ (10,0) [0|2550] "hPa" Dash
+
+CM_ BU_ Engine "Engine controller";
+CM_ BO_ 100 "Periodic engine data";
+CM_ SG_ 100 Rpm "Crankshaft speed";
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_DEF_DEF_ "GenMsgCycleTime" 0;
+BA_ "GenMsgCycleTime" BO_ 100 20;
+
+VAL_ 100 Gear 0 "Park" 1 "Drive" ;
+"#;
+
+#[test]
+fn parse_save_parse_is_semantically_equal() {
+    let db = parse(RICH);
+    let reparsed = parse(&to_dbc_string(&db).unwrap());
+    assert!(db.semantically_equals(&reparsed));
+    assert!(reparsed.semantically_equals(&db));
+
+    // ordering and key identity are ignored
+    let mut reordered = reparsed.clone();
+    reordered.messages_order.reverse();
+    reordered.signals_order.reverse();
+    assert!(db.semantically_equals(&reordered));
+}
+
+#[test]
+fn modified_copies_are_not_semantically_equal() {
+    let db = parse(RICH);
+    let edits: [fn(&mut can_tools::types::database::CanDatabase); 4] = [
+        |db| db.get_signal_by_name_mut("Rpm").unwrap().factor = 0.5,
+        |db| {
+            db.get_signal_by_name_mut("Gear")
+                .unwrap()
+                .value_table
+                .insert(2, "Reverse".to_string());
+        },
+        |db| {
+            db.get_message_by_name_mut("EngineData")
+                .unwrap()
+                .comment
+                .clear()
+        },
+        |db| db.get_node_by_name_mut("Engine").unwrap().comment.push('!'),
+    ];
+
+    for (i, edit) in edits.iter().enumerate() {
+        let mut copy = db.clone();
+        edit(&mut copy);
+        assert!(!db.semantically_equals(&copy), "edit {i}");
+        assert!(!copy.semantically_equals(&db), "edit {i}");
+    }
+}
+
+#[test]
+fn start_delay_and_repetitions_round_trip() {
+    let db = parse(&format!(
+        "{ORPHANS}\nBA_DEF_ BO_ \"GenMsgStartDelayTime\" INT 0 65535;\n\
+         BA_DEF_ BO_ \"GenMsgNrOfRepetition\" INT 0 999;\n\
+         BA_DEF_DEF_ \"GenMsgStartDelayTime\" 0;\n\
+         BA_DEF_DEF_ \"GenMsgNrOfRepetition\" 0;\n\
+         BA_ \"GenMsgStartDelayTime\" BO_ 1 250;\n\
+         BA_ \"GenMsgNrOfRepetition\" BO_ 1 3;\n"
+    ));
+    let message = db.get_message_by_name("Orphan").unwrap();
+    assert_eq!(message.start_delay_time, Some(250));
+    assert_eq!(message.nr_of_repetition, Some(3));
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(
+        text.contains("BA_ \"GenMsgStartDelayTime\" BO_ 1 250;"),
+        "{text}"
+    );
+    assert!(
+        text.contains("BA_ \"GenMsgNrOfRepetition\" BO_ 1 3;"),
+        "{text}"
+    );
+    let reparsed = parse(&text);
+    let message = reparsed.get_message_by_name("Orphan").unwrap();
+    assert_eq!(
+        (message.start_delay_time, message.nr_of_repetition),
+        (Some(250), Some(3))
+    );
+
+    // set through the typed fields alone, the definitions are written as well
+    let mut db = parse(ORPHANS);
+    let message = db.get_message_by_name_mut("Orphan").unwrap();
+    message.start_delay_time = Some(40);
+    message.nr_of_repetition = Some(2);
+    let text = to_dbc_string(&db).unwrap();
+    assert!(
+        text.contains("BA_DEF_ BO_ \"GenMsgStartDelayTime\" INT"),
+        "{text}"
+    );
+    let reparsed = parse(&text);
+    let message = reparsed.get_message_by_name("Orphan").unwrap();
+    assert_eq!(
+        (message.start_delay_time, message.nr_of_repetition),
+        (Some(40), Some(2))
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn write_failures_keep_the_io_error_as_source() {
+    use can_tools::save::save_to_file;
+    use can_tools::types::errors::DbcSaveError;
+    use std::error::Error as _;
+
+    // every write to /dev/full fails with "no space left on device"
+    let path = temp_dbc("full");
+    std::os::unix::fs::symlink("/dev/full", &path).unwrap();
+    let result = save_to_file(path.to_str().unwrap(), &parse(ORPHANS));
+    fs::remove_file(&path).unwrap();
+
+    let err = result.unwrap_err();
+    assert!(matches!(err, DbcSaveError::Write { .. }), "{err:?}");
+    let source = err
+        .source()
+        .and_then(|s| s.downcast_ref::<std::io::Error>())
+        .unwrap();
+    assert_eq!(source.kind(), std::io::ErrorKind::StorageFull);
+    assert!(err.to_string().contains(path.to_str().unwrap()));
+}
+
+#[test]
+fn environment_variable_value_descriptions_are_kept_verbatim() {
+    let env_var = "EV_ EnvMode: 0 [0|1] \"\" 0 1 DUMMY_NODE_VECTOR0 Vector__XXX;";
+    let descriptions = "VAL_ EnvMode 0 \"Auto\" 1 \"Manual\" ;";
+    let db = parse(&format!("{ORPHANS}\n{env_var}\n{descriptions}\n"));
+    assert_eq!(db.unparsed_lines, [env_var, descriptions]);
+    // not read as a value table of message 0 or any other message
+    assert!(db.iter_signals().all(|s| s.value_table.is_empty()));
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.lines().any(|l| l == descriptions), "{text}");
+    let reparsed = parse(&text);
+    assert_eq!(reparsed.unparsed_lines, [env_var, descriptions]);
+}
+
+#[test]
+fn multi_line_comments_survive_repeated_round_trips() {
+    let env_var = "EV_ EnvMode: 0 [0|1] \"\" 0 1 DUMMY_NODE_VECTOR0 Vector__XXX;";
+    let env_comment = "CM_ EV_ EnvMode \"mode\nswitch\";";
+    let db = parse(&format!(
+        "{ORPHANS}\n{env_var}\n\
+         CM_ \"network\n  notes\";\n\
+         CM_ BO_ 1 \"line one\nline two\";\n\
+         {env_comment}\n"
+    ));
+    assert_eq!(db.comment, "network\nnotes");
+    assert_eq!(
+        db.get_message_by_name("Orphan").unwrap().comment,
+        "line one\nline two"
+    );
+    assert_eq!(db.unparsed_lines, [env_var, env_comment]);
+
+    let text = to_dbc_string(&db).unwrap();
+    assert!(!text.lines().any(|l| l.starts_with("line two")), "{text}");
+    let reparsed = parse(&text);
+    assert!(reparsed.semantically_equals(&db));
+    assert_eq!(reparsed.unparsed_lines, db.unparsed_lines);
+    assert_eq!(to_dbc_string(&reparsed).unwrap(), text);
+}
+
+#[test]
+fn bus_type_and_baud_rates_survive_a_round_trip() {
+    let mut db = parse(ORPHANS);
+    db.set_bus_type(BusType::CanFd);
+    db.set_baudrate(500_000);
+    db.set_baudrate_canfd(2_000_000);
+
+    let text = to_dbc_string(&db).unwrap();
+    assert_eq!(text.matches("BA_ \"BusType\"").count(), 1, "{text}");
+    let reparsed = parse(&text);
+    assert_eq!(reparsed.bustype, BusType::CanFd);
+    assert_eq!(reparsed.baudrate(), Some(500_000));
+    assert_eq!(reparsed.baudrate_canfd(), Some(2_000_000));
+}
+
+#[test]
+fn stored_bus_type_spelling_is_kept_while_it_agrees_with_the_field() {
+    let mut db = parse(&format!(
+        "{ORPHANS}\nBA_DEF_ \"BusType\" STRING;\n\
+         BA_DEF_DEF_ \"BusType\" \"\";\n\
+         BA_ \"BusType\" \"Classic CAN\";\n"
+    ));
+    assert_eq!(db.bustype, BusType::Can);
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.contains("BA_ \"BusType\" \"Classic CAN\";"), "{text}");
+
+    // a bus type changed through the field replaces the stale attribute
+    db.bustype = BusType::CanFd;
+    let text = to_dbc_string(&db).unwrap();
+    assert!(text.contains("BA_ \"BusType\" \"CAN FD\";"), "{text}");
+    assert!(!text.contains("Classic CAN"), "{text}");
+    assert_eq!(parse(&text).bustype, BusType::CanFd);
+}